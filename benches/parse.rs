@@ -0,0 +1,22 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use parse_mrp_packet::borrowed::parse_mrp_data_ref;
+use parse_mrp_packet::parse_mrp_data;
+
+const PAYLOAD: [u8; 52] = *include_bytes!("../tests/fixtures/mrp_test.bin");
+
+fn bench_parse(c: &mut Criterion) {
+    c.bench_function("parse_mrp_data", |b| {
+        b.iter(|| parse_mrp_data(black_box(&PAYLOAD)).unwrap())
+    });
+    c.bench_function("parse_mrp_data_ref", |b| {
+        b.iter(|| {
+            let mrp_ref = parse_mrp_data_ref(black_box(&PAYLOAD)).unwrap();
+            for tlv in &mrp_ref {
+                black_box(tlv.unwrap().data().unwrap());
+            }
+        })
+    });
+}
+
+criterion_group!(benches, bench_parse);
+criterion_main!(benches);
@@ -0,0 +1,38 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use parse_mrp_packet::{
+    borrowed, frame, parse_mrp_data, parse_mrp_data_with_policy, parse_mrp_data_with_remainder,
+    parse_mrp_data_with_warnings, parse_mrp_tlvs_only, pcap, reader, transmit, MRPParser,
+    UnknownTlvPolicy,
+};
+
+// Run with `cargo +nightly fuzz run parse -- tests/corpus` from the crate root to start from
+// the checked-in regression inputs. Crashes found here belong in tests/corpus/.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(mrp_data) = parse_mrp_data(data) {
+        let _ = (
+            mrp_data.to_string(),
+            mrp_data.summary(),
+            mrp_data.validate(),
+        );
+        let _ = transmit::pre_transmit_check(&mrp_data);
+        // Parsed values came from one-byte length fields, so they always fit again.
+        let encoded = mrp_data.to_bytes().expect("parsed PDU must re-encode");
+        let reparsed = parse_mrp_data(&encoded).expect("re-encoded PDU must parse");
+        assert_eq!(reparsed.to_bytes(), Ok(encoded));
+    }
+    let _ = parse_mrp_data_with_policy(data, UnknownTlvPolicy::Reject);
+    let _ = parse_mrp_data_with_warnings(data);
+    let _ = parse_mrp_tlvs_only(data);
+    let _ = parse_mrp_data_with_remainder(data);
+    let _ = MRPParser::new().strict_version(true).parse(data);
+    if let Ok(mrp_ref) = borrowed::parse_mrp_data_ref(data) {
+        for tlv in &mrp_ref {
+            let _ = tlv.map(|tlv| tlv.data());
+        }
+    }
+    reader::TlvReader::new(data).for_each(drop);
+    let _ = frame::parse_mrp_frame(data);
+    pcap::read_mrp_from_pcap(data).for_each(drop);
+});
@@ -0,0 +1,211 @@
+use crate::{
+    parse_tlv_data, try_parse_u16, MRPData, MRPParseError, MRPTLVData, MRPTLVHeader,
+    UnknownTlvPolicy, MRP_MAX_PDU_SIZE,
+};
+use alloc::vec::Vec;
+
+/// An MRP PDU borrowed from the capture buffer.
+///
+/// Only the version is decoded up front; TLVs are split and decoded lazily by [`iter`],
+/// so walking a PDU this way does not allocate.
+///
+/// [`iter`]: MRPDataRef::iter
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MRPDataRef<'a> {
+    pub version: u16,
+    data: &'a [u8],
+}
+
+/// A single TLV whose value still points into the capture buffer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MRPTLVRef<'a> {
+    pub tlv_type: u8,
+    pub value: &'a [u8],
+}
+
+impl<'a> MRPTLVRef<'a> {
+    /// Decodes the value, keeping unknown TLV types as [`MRPTLVData::Unknown`].
+    pub fn data(&self) -> Result<MRPTLVData, MRPParseError> {
+        self.data_with_policy(UnknownTlvPolicy::Keep)
+    }
+
+    pub fn data_with_policy(&self, policy: UnknownTlvPolicy) -> Result<MRPTLVData, MRPParseError> {
+        parse_tlv_data(self.tlv_type, self.value, policy)
+    }
+
+    pub fn to_owned(self) -> Result<MRPTLVHeader, MRPParseError> {
+        self.to_owned_with_policy(UnknownTlvPolicy::Keep)
+    }
+
+    pub fn to_owned_with_policy(
+        self,
+        policy: UnknownTlvPolicy,
+    ) -> Result<MRPTLVHeader, MRPParseError> {
+        let data = self.data_with_policy(policy)?;
+        Ok(MRPTLVHeader {
+            tlv_type: self.tlv_type,
+            // The wire length, even where it is wrong for the type, so `validate` can report it.
+            length: self.value.len() as u8,
+            data,
+        })
+    }
+}
+
+/// Yields the TLVs of a PDU one at a time, stopping after MRP_End.
+///
+/// Framing errors are yielded once, after which the iterator is exhausted.
+#[derive(Debug, Clone)]
+pub struct MrpTlvIter<'a> {
+    data: &'a [u8],
+    offset: usize,
+    done: bool,
+}
+
+impl<'a> MrpTlvIter<'a> {
+    /// Walks the TLVs of `data` starting at `offset`; error offsets are relative to `data`.
+    pub(crate) fn new(data: &'a [u8], offset: usize) -> Self {
+        MrpTlvIter {
+            data,
+            offset,
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for MrpTlvIter<'a> {
+    type Item = Result<MRPTLVRef<'a>, MRPParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.offset >= self.data.len() {
+            return None;
+        }
+        let (data, offset) = (self.data, self.offset);
+        if offset + 2 > data.len() {
+            self.done = true;
+            return Some(Err(MRPParseError::TruncatedHeader { offset }));
+        }
+
+        let tlv_type = data[offset];
+        let length = data[offset + 1] as usize;
+        if offset + 2 + length > data.len() {
+            self.done = true;
+            return Some(Err(MRPParseError::TruncatedTlvValue {
+                offset,
+                tlv_type,
+                expected: length,
+                available: data.len() - offset - 2,
+            }));
+        }
+
+        self.offset += 2 + length;
+        // Anything after MRP_End is Ethernet padding, not TLVs.
+        self.done = tlv_type == 0x00;
+        Some(Ok(MRPTLVRef {
+            tlv_type,
+            value: &data[offset + 2..offset + 2 + length],
+        }))
+    }
+}
+
+impl<'a> MRPDataRef<'a> {
+    pub fn iter(&self) -> MrpTlvIter<'a> {
+        MrpTlvIter::new(self.data, 2)
+    }
+
+    /// The raw PDU this view was parsed from.
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// Decodes every TLV into an owned [`MRPData`], as [`crate::parse_mrp_data`] would.
+    pub fn to_owned(self) -> Result<MRPData, MRPParseError> {
+        self.to_owned_with_policy(UnknownTlvPolicy::Keep)
+    }
+
+    pub fn to_owned_with_policy(self, policy: UnknownTlvPolicy) -> Result<MRPData, MRPParseError> {
+        Ok(MRPData {
+            version: self.version,
+            tlv_headers: collect_tlvs(self.iter(), policy)?,
+        })
+    }
+}
+
+impl<'a> IntoIterator for &MRPDataRef<'a> {
+    type Item = Result<MRPTLVRef<'a>, MRPParseError>;
+    type IntoIter = MrpTlvIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+pub(crate) fn collect_tlvs(
+    iter: MrpTlvIter<'_>,
+    policy: UnknownTlvPolicy,
+) -> Result<Vec<MRPTLVHeader>, MRPParseError> {
+    iter.map(|tlv| tlv?.to_owned_with_policy(policy)).collect()
+}
+
+/// Checks the PDU size and reads the version without touching the TLVs.
+pub fn parse_mrp_data_ref(data: &[u8]) -> Result<MRPDataRef<'_>, MRPParseError> {
+    if data.len() > MRP_MAX_PDU_SIZE {
+        return Err(MRPParseError::FrameTooLong {
+            actual: data.len(),
+            max: MRP_MAX_PDU_SIZE,
+        });
+    }
+    let version = try_parse_u16(data)?;
+    Ok(MRPDataRef { version, data })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_mrp_data;
+    use crate::validate::ValidationIssue;
+    use crate::TEST_PAYLOAD;
+
+    #[test]
+    fn test_borrowed_matches_owned() {
+        let mrp_ref = parse_mrp_data_ref(&TEST_PAYLOAD).unwrap();
+        assert_eq!(mrp_ref.version, 1);
+        let types: Vec<u8> = mrp_ref.iter().map(|tlv| tlv.unwrap().tlv_type).collect();
+        assert_eq!(types, [0x02, 0x01, 0x7f, 0x00]);
+        assert_eq!(mrp_ref.to_owned(), parse_mrp_data(&TEST_PAYLOAD));
+    }
+
+    #[test]
+    fn test_borrowed_reports_truncation_once() {
+        let mrp_ref = parse_mrp_data_ref(&TEST_PAYLOAD[..30]).unwrap();
+        let mut iter = mrp_ref.iter();
+        assert!(iter.next().unwrap().is_ok());
+        assert_eq!(
+            iter.next(),
+            Some(Err(MRPParseError::TruncatedTlvValue {
+                offset: 22,
+                tlv_type: 0x01,
+                expected: 0x12,
+                available: 6,
+            }))
+        );
+        assert_eq!(iter.next(), None);
+        assert_eq!(mrp_ref.to_owned(), parse_mrp_data(&TEST_PAYLOAD[..30]));
+    }
+
+    #[test]
+    fn test_end_keeps_wire_length() {
+        let mut payload = TEST_PAYLOAD.to_vec();
+        payload[51] = 0x02;
+        payload.extend([0x00, 0x00]);
+        let mrp_data = parse_mrp_data_ref(&payload).unwrap().to_owned().unwrap();
+        assert_eq!(mrp_data.tlv_headers[3].length, 2);
+        assert_eq!(
+            mrp_data.validate(),
+            vec![ValidationIssue::WrongTlvLength {
+                tlv_type: 0x00,
+                expected: 0,
+                actual: 2,
+            }]
+        );
+    }
+}
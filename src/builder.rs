@@ -0,0 +1,280 @@
+use crate::validate::{Severity, ValidationIssue};
+use crate::{
+    EncodeError, MRPCommonData, MRPData, MRPLinkData, MRPOptionData, MRPSubTlv, MRPTLVData,
+    MRPTestData, MRPTopologyChangeData, MacAddress, PortRole, RingState, MRP_VERSION,
+};
+use alloc::vec;
+use alloc::vec::Vec;
+use uuid::Uuid;
+
+/// Domain UUID of the MRP default domain, used when no Common TLV is given.
+pub const DEFAULT_DOMAIN_UUID: Uuid = Uuid::from_u128(u128::MAX);
+
+/// Assembles an [`MRPData`] with TLV types and lengths filled in.
+///
+/// TLVs are emitted in wire order regardless of call order: the frame TLVs, MRP_Common,
+/// MRP_Option TLVs and finally MRP_End.
+#[derive(Debug)]
+pub struct MrpFrameBuilder {
+    version: u16,
+    frame_tlvs: Vec<MRPTLVData>,
+    common: Option<MRPCommonData>,
+    options: Vec<MRPOptionData>,
+}
+
+impl Default for MrpFrameBuilder {
+    fn default() -> Self {
+        MrpFrameBuilder {
+            version: MRP_VERSION,
+            frame_tlvs: Vec::new(),
+            common: None,
+            options: Vec::new(),
+        }
+    }
+}
+
+impl MrpFrameBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn version(mut self, version: u16) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn test(
+        self,
+        prio: u16,
+        sa: MacAddress,
+        port_role: PortRole,
+        ring_state: RingState,
+        transition: u16,
+        timestamp: u32,
+    ) -> Self {
+        self.tlv(MRPTLVData::MRPTest(MRPTestData::new(
+            prio, sa, port_role, ring_state, transition, timestamp,
+        )))
+    }
+
+    pub fn topology_change(self, prio: u16, sa: MacAddress, interval: u16) -> Self {
+        self.tlv(MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(
+            prio, sa, interval,
+        )))
+    }
+
+    pub fn link_down(
+        self,
+        sa: MacAddress,
+        port_role: PortRole,
+        interval: u16,
+        blocked: u16,
+    ) -> Self {
+        self.tlv(MRPTLVData::MRPLinkDown(MRPLinkData::new(
+            sa, port_role, interval, blocked,
+        )))
+    }
+
+    pub fn link_up(self, sa: MacAddress, port_role: PortRole, interval: u16, blocked: u16) -> Self {
+        self.tlv(MRPTLVData::MRPLinkUp(MRPLinkData::new(
+            sa, port_role, interval, blocked,
+        )))
+    }
+
+    /// Adds any other frame TLV, e.g. one of the interconnection TLVs.
+    pub fn tlv(mut self, data: MRPTLVData) -> Self {
+        self.frame_tlvs.push(data);
+        self
+    }
+
+    /// Defaults to sequence ID 0 in [`DEFAULT_DOMAIN_UUID`] if never called.
+    pub fn common(mut self, sequence_id: u16, domain_uuid: Uuid) -> Self {
+        self.common = Some(MRPCommonData::new(sequence_id, domain_uuid));
+        self
+    }
+
+    pub fn option(mut self, manufacturer_oui: [u8; 3], sub_tlvs: Vec<MRPSubTlv>) -> Self {
+        self.options
+            .push(MRPOptionData::new(manufacturer_oui, sub_tlvs));
+        self
+    }
+
+    /// Builds the PDU, rejecting it if [`MRPData::validate`] reports any error-severity issue.
+    pub fn build(self) -> Result<MRPData, Vec<ValidationIssue>> {
+        let mut mrp_data = MRPData::new(self.version);
+        for data in self.frame_tlvs {
+            push(&mut mrp_data, data)?;
+        }
+        let common = self
+            .common
+            .unwrap_or_else(|| MRPCommonData::new(0, DEFAULT_DOMAIN_UUID));
+        push(&mut mrp_data, MRPTLVData::MRPCommon(common))?;
+        for option in self.options {
+            push(&mut mrp_data, MRPTLVData::MRPOption(option))?;
+        }
+        push(&mut mrp_data, MRPTLVData::MRPEnd)?;
+
+        let errors: Vec<ValidationIssue> = mrp_data
+            .validate()
+            .into_iter()
+            .filter(|issue| issue.severity() == Severity::Error)
+            .collect();
+        if errors.is_empty() {
+            Ok(mrp_data)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Appends `data`, reporting a TLV that cannot be encoded as a validation error.
+fn push(mrp_data: &mut MRPData, data: MRPTLVData) -> Result<(), Vec<ValidationIssue>> {
+    let (tlv_type, length) = (data.type_byte(), data.payload_length());
+    mrp_data.push(data).map_err(|err| {
+        vec![match err {
+            EncodeError::MisplacedEd1 { index } => ValidationIssue::MisplacedEd1 { index },
+            _ => ValidationIssue::ValueTooLong { tlv_type, length },
+        }]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_PAYLOAD;
+    use crate::{parse_mrp_data, MRPInTestData, SIEMENS_OUI};
+
+    fn sa() -> MacAddress {
+        MacAddress::from([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22])
+    }
+
+    #[test]
+    fn test_build_reference_payload() {
+        let mrp_data = MrpFrameBuilder::new()
+            .option(
+                SIEMENS_OUI,
+                vec![MRPSubTlv::Ed1 {
+                    ed1_type: 0x00,
+                    manufacturer_data: 0x0000,
+                }],
+            )
+            .common(
+                0x057e,
+                Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap(),
+            )
+            .test(
+                0xa000,
+                sa(),
+                PortRole::Primary,
+                RingState::Open,
+                0x0001,
+                0x19fa3fd4,
+            )
+            .build()
+            .unwrap();
+        assert!(mrp_data.iter().all(|header| header.is_consistent()));
+        assert_eq!(mrp_data.to_bytes().unwrap(), TEST_PAYLOAD);
+        assert_eq!(
+            parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap(),
+            mrp_data
+        );
+    }
+
+    #[test]
+    fn test_omitted_common_uses_default_domain() {
+        let mrp_data = MrpFrameBuilder::new()
+            .topology_change(0x8000, sa(), 10)
+            .build()
+            .unwrap();
+        assert_eq!(mrp_data.sequence_id(), Some(0));
+        assert_eq!(mrp_data.domain_uuid(), Some(DEFAULT_DOMAIN_UUID));
+        assert_eq!(mrp_data.validate(), vec![]);
+        assert_eq!(
+            parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap(),
+            mrp_data
+        );
+    }
+
+    #[test]
+    fn test_build_other_frames() {
+        for builder in [
+            MrpFrameBuilder::new().link_down(sa(), PortRole::Secondary, 20, 1),
+            MrpFrameBuilder::new().link_up(sa(), PortRole::Primary, 20, 0),
+            MrpFrameBuilder::new().tlv(MRPTLVData::MRPInTest(MRPInTestData::new(
+                1,
+                sa(),
+                PortRole::Interconnection,
+                RingState::Closed,
+                0,
+                0,
+            ))),
+        ] {
+            let mrp_data = builder.common(7, DEFAULT_DOMAIN_UUID).build().unwrap();
+            assert_eq!(
+                parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap(),
+                mrp_data
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_rejects_nonsense() {
+        let err = MrpFrameBuilder::new()
+            .test(0xa000, sa(), PortRole::Primary, RingState::Open, 0, 0)
+            .test(0xa000, sa(), PortRole::Secondary, RingState::Open, 0, 0)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, vec![ValidationIssue::MultipleFrameTlvs]);
+
+        let err = MrpFrameBuilder::new()
+            .common(1, DEFAULT_DOMAIN_UUID)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, vec![ValidationIssue::MissingFrameTlv]);
+
+        let err = MrpFrameBuilder::new()
+            .test(0xa000, sa(), PortRole::Primary, RingState::Open, 0, 0)
+            .tlv(MRPTLVData::MRPCommon(MRPCommonData::new(
+                1,
+                DEFAULT_DOMAIN_UUID,
+            )))
+            .build()
+            .unwrap_err();
+        assert_eq!(err, vec![ValidationIssue::DuplicateCommon]);
+
+        let err = MrpFrameBuilder::new()
+            .test(0xa000, sa(), PortRole::Primary, RingState::Open, 0, 0)
+            .option(
+                [0x00, 0x80, 0x63],
+                vec![MRPSubTlv::Raw {
+                    sub_type: 0x80,
+                    value: vec![0; 251],
+                }],
+            )
+            .build()
+            .unwrap_err();
+        assert_eq!(
+            err,
+            vec![ValidationIssue::ValueTooLong {
+                tlv_type: 0x7f,
+                length: 256,
+            }]
+        );
+        let err = MrpFrameBuilder::new()
+            .test(0xa000, sa(), PortRole::Primary, RingState::Open, 0, 0)
+            .option(SIEMENS_OUI, vec![MRPSubTlv::AutoMgr])
+            .build()
+            .unwrap_err();
+        assert_eq!(err, vec![ValidationIssue::MisplacedEd1 { index: 0 }]);
+    }
+
+    #[test]
+    fn test_build_allows_other_versions() {
+        let mrp_data = MrpFrameBuilder::new()
+            .version(2)
+            .topology_change(0x8000, sa(), 10)
+            .build()
+            .unwrap();
+        assert_eq!(mrp_data.version, 2);
+    }
+}
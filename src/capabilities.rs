@@ -0,0 +1,147 @@
+use crate::{MrpTlvType, SIEMENS_OUI};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Input framings the crate can decode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LinkType {
+    /// A bare MRP PDU starting at the version field, as passed to `parse_mrp_data`.
+    MrpPdu,
+    /// A full Ethernet II frame, as passed to `frame::parse_mrp_frame`.
+    Ethernet,
+}
+
+/// What this build of the crate can decode, for tools that query it at runtime.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Capabilities {
+    pub crate_version: &'static str,
+    pub tlv_types: Vec<MrpTlvType>,
+    /// OUIs with a dedicated MRP_Option decoder.
+    pub vendor_ouis: Vec<[u8; 3]>,
+    pub features: Vec<&'static str>,
+    pub link_types: Vec<LinkType>,
+}
+
+fn enabled_features() -> Vec<&'static str> {
+    let mut features = Vec::new();
+    if cfg!(feature = "std") {
+        features.push("std");
+    }
+    if cfg!(feature = "serde") {
+        features.push("serde");
+    }
+    if cfg!(feature = "msgpack") {
+        features.push("msgpack");
+    }
+    if cfg!(feature = "proto") {
+        features.push("proto");
+    }
+    if cfg!(feature = "json") {
+        features.push("json");
+    }
+    if cfg!(feature = "pcap") {
+        features.push("pcap");
+    }
+    features
+}
+
+/// TLV types this build decodes, in type-byte order.
+///
+/// The interconnection TLVs are part of the base decoder rather than behind a cargo feature,
+/// so every build reports the same set. There are no FFI, Python or WASM layers in this
+/// crate; bindings call [`capabilities`] themselves.
+const SUPPORTED_TLV_TYPES: &[MrpTlvType] = &MrpTlvType::ALL;
+
+/// Whether this build decodes `tlv_type`; an unsupported type is kept as
+/// [`MRPTLVData::Unknown`](crate::MRPTLVData::Unknown).
+pub fn supports_tlv(tlv_type: MrpTlvType) -> bool {
+    SUPPORTED_TLV_TYPES.contains(&tlv_type)
+}
+
+/// [`supports_tlv`] for a raw type byte, e.g. one read from an unparsed header.
+pub fn supports_tlv_byte(tlv_type: u8) -> bool {
+    MrpTlvType::try_from(tlv_type).is_ok_and(supports_tlv)
+}
+
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        crate_version: env!("CARGO_PKG_VERSION"),
+        tlv_types: SUPPORTED_TLV_TYPES.to_vec(),
+        vendor_ouis: vec![SIEMENS_OUI],
+        features: enabled_features(),
+        link_types: vec![LinkType::MrpPdu, LinkType::Ethernet],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_capabilities() {
+        let caps = capabilities();
+        assert_eq!(caps.crate_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            caps.tlv_types,
+            vec![
+                MrpTlvType::End,
+                MrpTlvType::Common,
+                MrpTlvType::Test,
+                MrpTlvType::TopologyChange,
+                MrpTlvType::LinkDown,
+                MrpTlvType::LinkUp,
+                MrpTlvType::InTest,
+                MrpTlvType::InTopologyChange,
+                MrpTlvType::InLinkDown,
+                MrpTlvType::InLinkUp,
+                MrpTlvType::InLinkStatusPoll,
+                MrpTlvType::Option,
+            ]
+        );
+        assert_eq!(caps.vendor_ouis, vec![SIEMENS_OUI]);
+        assert_eq!(caps.link_types, vec![LinkType::MrpPdu, LinkType::Ethernet]);
+    }
+
+    #[cfg(all(
+        feature = "std",
+        not(any(
+            feature = "serde",
+            feature = "msgpack",
+            feature = "proto",
+            feature = "json",
+            feature = "pcap"
+        ))
+    ))]
+    #[test]
+    fn test_default_features() {
+        assert_eq!(capabilities().features, vec!["std"]);
+    }
+
+    #[test]
+    fn test_supports_tlv() {
+        for tlv_type in MrpTlvType::ALL {
+            assert!(supports_tlv(tlv_type));
+            assert!(supports_tlv_byte(tlv_type.into()));
+        }
+        assert!(!supports_tlv_byte(0x0b));
+        assert!(!supports_tlv_byte(0x80));
+        assert!(!supports_tlv_byte(0xff));
+    }
+
+    #[test]
+    fn test_interconnection_tlvs_are_always_supported() {
+        // No feature gates them, so every build lists them.
+        let caps = capabilities();
+        for tlv_type in [
+            MrpTlvType::InTest,
+            MrpTlvType::InTopologyChange,
+            MrpTlvType::InLinkDown,
+            MrpTlvType::InLinkUp,
+            MrpTlvType::InLinkStatusPoll,
+        ] {
+            assert!(caps.tlv_types.contains(&tlv_type));
+        }
+    }
+}
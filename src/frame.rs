@@ -0,0 +1,242 @@
+use crate::transmit::{MC_CONTROL, MC_INCONTROL, MC_INTEST, MC_TEST};
+use crate::{parse_mac_address, parse_mrp_data, MRPData, MRPParseError, MacAddress};
+use core::fmt;
+
+/// EtherType of MRP PDUs.
+pub const MRP_ETHERTYPE: u16 = 0x88e3;
+
+/// TPIDs recognised as VLAN tags: 802.1Q C-tags, 802.1ad S-tags and the legacy 0x9100 QinQ tag.
+const VLAN_TPIDS: [u16; 3] = [0x8100, 0x88a8, 0x9100];
+
+/// An MRP PDU together with the Ethernet header it arrived in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MRPFrame {
+    pub dst: MacAddress,
+    pub src: MacAddress,
+    /// VLAN ID of the innermost VLAN tag, if the frame is tagged.
+    pub vlan: Option<u16>,
+    pub data: MRPData,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FrameError {
+    /// The frame ends before the Ethernet header (including VLAN tags) does.
+    TooShort,
+    /// The destination is not one of the MRP multicast groups.
+    NotMrpDestination(MacAddress),
+    /// The EtherType after any VLAN tags is not [`MRP_ETHERTYPE`].
+    NotMrpEtherType(u16),
+    /// The frame is addressed as MRP but the payload does not parse.
+    Malformed(MRPParseError),
+}
+
+impl FrameError {
+    /// Whether the frame is simply not MRP, as opposed to a malformed MRP frame.
+    pub fn is_not_mrp(&self) -> bool {
+        !matches!(self, FrameError::Malformed(_))
+    }
+}
+
+impl fmt::Display for FrameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameError::TooShort => write!(f, "frame is too short for an Ethernet header"),
+            FrameError::NotMrpDestination(dst) => {
+                write!(f, "destination {} is not an MRP multicast address", dst)
+            }
+            FrameError::NotMrpEtherType(ether_type) => {
+                write!(f, "EtherType {:#06x} is not MRP", ether_type)
+            }
+            FrameError::Malformed(err) => write!(f, "malformed MRP payload: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for FrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FrameError::Malformed(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<MRPParseError> for FrameError {
+    fn from(err: MRPParseError) -> Self {
+        FrameError::Malformed(err)
+    }
+}
+
+fn read_u16(frame: &[u8], offset: usize) -> Result<u16, FrameError> {
+    frame
+        .get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or(FrameError::TooShort)
+}
+
+/// Parses an Ethernet II frame (without FCS) carrying an MRP PDU.
+///
+/// Any number of VLAN tags between the source address and the EtherType are skipped.
+pub fn parse_mrp_frame(frame: &[u8]) -> Result<MRPFrame, FrameError> {
+    let dst = frame
+        .get(..6)
+        .and_then(|bytes| MacAddress::try_from(bytes).ok())
+        .ok_or(FrameError::TooShort)?;
+    if ![MC_TEST, MC_CONTROL, MC_INTEST, MC_INCONTROL].contains(&dst) {
+        return Err(FrameError::NotMrpDestination(dst));
+    }
+    let src = frame
+        .get(6..12)
+        .and_then(|bytes| MacAddress::try_from(bytes).ok())
+        .ok_or(FrameError::TooShort)?;
+
+    let mut offset = 12;
+    let mut vlan = None;
+    let mut ether_type = read_u16(frame, offset)?;
+    while VLAN_TPIDS.contains(&ether_type) {
+        vlan = Some(read_u16(frame, offset + 2)? & 0x0fff);
+        offset += 4;
+        ether_type = read_u16(frame, offset)?;
+    }
+    if ether_type != MRP_ETHERTYPE {
+        return Err(FrameError::NotMrpEtherType(ether_type));
+    }
+
+    Ok(MRPFrame {
+        dst,
+        src,
+        vlan,
+        data: parse_mrp_data(&frame[offset + 2..])?,
+    })
+}
+
+/// Whether the frame's source and destination addresses are the same.
+///
+/// MRP frames go to multicast groups, so this only holds for a parsed frame whose source
+/// claims that same group address.
+pub fn is_loopback_frame(frame: &MRPFrame) -> bool {
+    frame.src == frame.dst
+}
+
+/// Parses an MRP PDU preceded by the 6-byte source MAC address, for drivers that hand over
+/// the payload with the Ethernet header stripped but keep the sender.
+pub fn parse_mrp_data_with_source_mac(
+    frame: &[u8],
+) -> Result<(MacAddress, MRPData), MRPParseError> {
+    if frame.len() < 8 {
+        return Err(MRPParseError::InsufficientData {
+            needed: 8,
+            available: frame.len(),
+        });
+    }
+    let (src, pdu) = frame.split_at(6);
+    Ok((parse_mac_address(src), parse_mrp_data(pdu)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_PAYLOAD;
+
+    fn frame(tags: &[[u8; 4]]) -> Vec<u8> {
+        let mut frame = vec![0x01, 0x15, 0x4e, 0x00, 0x00, 0x01];
+        frame.extend_from_slice(&[0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        for tag in tags {
+            frame.extend_from_slice(tag);
+        }
+        frame.extend_from_slice(&[0x88, 0xe3]);
+        frame.extend_from_slice(&TEST_PAYLOAD);
+        frame
+    }
+
+    #[test]
+    fn test_untagged_frame() {
+        let mrp_frame = parse_mrp_frame(&frame(&[])).unwrap();
+        assert_eq!(mrp_frame.dst, MC_TEST);
+        assert_eq!(mrp_frame.src.to_string(), "00:0e:8c:e0:2f:22");
+        assert_eq!(mrp_frame.vlan, None);
+        assert_eq!(mrp_frame.data, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+    }
+
+    #[test]
+    fn test_single_tagged_frame() {
+        let mrp_frame = parse_mrp_frame(&frame(&[[0x81, 0x00, 0xe0, 0x00]])).unwrap();
+        assert_eq!(mrp_frame.vlan, Some(0));
+        assert_eq!(mrp_frame.data, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+    }
+
+    #[test]
+    fn test_double_tagged_frame() {
+        let tags = [[0x88, 0xa8, 0x00, 0x64], [0x81, 0x00, 0xe0, 0x0a]];
+        let mrp_frame = parse_mrp_frame(&frame(&tags)).unwrap();
+        assert_eq!(mrp_frame.vlan, Some(10));
+        assert_eq!(mrp_frame.data, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+    }
+
+    #[test]
+    fn test_non_mrp_frames() {
+        let mut ipv4 = frame(&[]);
+        ipv4[12..14].copy_from_slice(&[0x08, 0x00]);
+        let err = parse_mrp_frame(&ipv4).unwrap_err();
+        assert_eq!(err, FrameError::NotMrpEtherType(0x0800));
+        assert!(err.is_not_mrp());
+
+        let mut unicast = frame(&[]);
+        unicast[..6].copy_from_slice(&[0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]);
+        assert!(matches!(
+            parse_mrp_frame(&unicast),
+            Err(FrameError::NotMrpDestination(_))
+        ));
+
+        assert_eq!(
+            parse_mrp_frame(&frame(&[])[..13]),
+            Err(FrameError::TooShort)
+        );
+    }
+
+    #[test]
+    fn test_malformed_payload() {
+        let mut truncated = frame(&[]);
+        truncated.truncate(14 + 30);
+        let err = parse_mrp_frame(&truncated).unwrap_err();
+        assert!(matches!(
+            err,
+            FrameError::Malformed(MRPParseError::TruncatedTlvValue { .. })
+        ));
+        assert!(!err.is_not_mrp());
+    }
+
+    #[test]
+    fn test_parse_mrp_data_with_source_mac() {
+        let mut prefixed = vec![0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22];
+        prefixed.extend_from_slice(&TEST_PAYLOAD);
+        let (src, mrp_data) = parse_mrp_data_with_source_mac(&prefixed).unwrap();
+        assert_eq!(src.to_string(), "00:0e:8c:e0:2f:22");
+        assert_eq!(mrp_data, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+
+        assert_eq!(
+            parse_mrp_data_with_source_mac(&prefixed[..7]),
+            Err(MRPParseError::InsufficientData {
+                needed: 8,
+                available: 7,
+            })
+        );
+        assert!(matches!(
+            parse_mrp_data_with_source_mac(&prefixed[..36]),
+            Err(MRPParseError::TruncatedTlvValue { .. })
+        ));
+    }
+
+    #[test]
+    fn test_is_loopback_frame() {
+        let mut mrp_frame = parse_mrp_frame(&frame(&[])).unwrap();
+        assert!(!is_loopback_frame(&mrp_frame));
+
+        let mut looped = frame(&[]);
+        looped.copy_within(..6, 6);
+        mrp_frame = parse_mrp_frame(&looped).unwrap();
+        assert_eq!(mrp_frame.src, MC_TEST);
+        assert!(is_loopback_frame(&mrp_frame));
+    }
+}
@@ -1,313 +1,6191 @@
-use std::fmt;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+pub mod borrowed;
+pub mod builder;
+pub mod capabilities;
+pub mod frame;
+pub mod monitor;
+#[cfg(feature = "pcap")]
+pub mod pcap;
+#[cfg(feature = "proto")]
+pub mod proto;
+pub mod reader;
+pub mod redact;
+#[cfg(feature = "serde")]
+mod serde_impls;
+pub mod transmit;
+pub mod validate;
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+use core::time::Duration;
 use uuid::Uuid;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRPData {
     pub version: u16,
     pub tlv_headers: Vec<MRPTLVHeader>,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRPTLVHeader {
     pub tlv_type: u8,
     pub length: u8,
     pub data: MRPTLVData,
 }
 
-#[derive(Debug, PartialEq)]
+/// With `serde`, human-readable formats use `{"type": "test", "data": {..}}`; binary
+/// formats store the type byte and the TLV value bytes.
+///
+/// Not `Copy`, since MRP_Option and unknown TLVs carry variable-length data; the fixed-size
+/// TLV structs are.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MRPTLVData {
     MRPTest(MRPTestData),
     MRPCommon(MRPCommonData),
+    MRPTopologyChange(MRPTopologyChangeData),
+    MRPLinkDown(MRPLinkData),
+    MRPLinkUp(MRPLinkData),
+    MRPInTest(MRPInTestData),
+    MRPInTopologyChange(MRPInTopologyChangeData),
+    MRPInLinkDown(MRPInLinkData),
+    MRPInLinkUp(MRPInLinkData),
+    MRPInLinkStatusPoll(MRPInLinkStatusPollData),
     MRPOption(MRPOptionData),
     MRPEnd,
+    /// A TLV type this crate does not decode, kept as its raw value bytes.
+    Unknown {
+        tlv_type: u8,
+        raw: Vec<u8>,
+    },
+}
+
+/// What an MRP frame is, named after its first TLV other than MRP_Common, as Wireshark
+/// labels it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MrpFrameKind {
+    Test,
+    TopologyChange,
+    /// MRP_LinkDown or MRP_LinkUp.
+    LinkChange,
+    InTest,
+    InTopologyChange,
+    /// MRP_InLinkDown or MRP_InLinkUp.
+    InLinkChange,
+    InLinkStatusPoll,
+    /// No frame TLV, e.g. a PDU holding only Option or End TLVs.
+    Unknown,
+}
+
+/// TLV types defined by IEC 62439-2, whether or not this build decodes them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MrpTlvType {
+    End = 0x00,
+    Common = 0x01,
+    Test = 0x02,
+    TopologyChange = 0x03,
+    LinkDown = 0x04,
+    LinkUp = 0x05,
+    InTest = 0x06,
+    InTopologyChange = 0x07,
+    InLinkDown = 0x08,
+    InLinkUp = 0x09,
+    InLinkStatusPoll = 0x0a,
+    Option = 0x7f,
+}
+
+impl MrpTlvType {
+    pub const ALL: [MrpTlvType; 12] = [
+        MrpTlvType::End,
+        MrpTlvType::Common,
+        MrpTlvType::Test,
+        MrpTlvType::TopologyChange,
+        MrpTlvType::LinkDown,
+        MrpTlvType::LinkUp,
+        MrpTlvType::InTest,
+        MrpTlvType::InTopologyChange,
+        MrpTlvType::InLinkDown,
+        MrpTlvType::InLinkUp,
+        MrpTlvType::InLinkStatusPoll,
+        MrpTlvType::Option,
+    ];
+}
+
+impl From<MrpTlvType> for u8 {
+    fn from(tlv_type: MrpTlvType) -> Self {
+        tlv_type as u8
+    }
+}
+
+impl TryFrom<u8> for MrpTlvType {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        MrpTlvType::ALL
+            .into_iter()
+            .find(|tlv_type| *tlv_type as u8 == value)
+            .ok_or(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PortRole {
+    #[default]
+    Primary,
+    Secondary,
+    /// The port connecting a ring to an MRP interconnection.
+    Interconnection,
+    /// Any other value, kept so it serializes back unchanged.
+    Unknown(u16),
+}
+
+impl From<u16> for PortRole {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0000 => PortRole::Primary,
+            0x0001 => PortRole::Secondary,
+            0x0002 => PortRole::Interconnection,
+            other => PortRole::Unknown(other),
+        }
+    }
+}
+
+impl From<PortRole> for u16 {
+    fn from(role: PortRole) -> Self {
+        match role {
+            PortRole::Primary => 0x0000,
+            PortRole::Secondary => 0x0001,
+            PortRole::Interconnection => 0x0002,
+            PortRole::Unknown(value) => value,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RingState {
+    #[default]
+    Open,
+    Closed,
+    /// Any other value, kept so it serializes back unchanged.
+    Unknown(u16),
+}
+
+impl From<u16> for RingState {
+    fn from(value: u16) -> Self {
+        match value {
+            0x0000 => RingState::Open,
+            0x0001 => RingState::Closed,
+            other => RingState::Unknown(other),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+impl From<RingState> for u16 {
+    fn from(state: RingState) -> Self {
+        match state {
+            RingState::Open => 0x0000,
+            RingState::Closed => 0x0001,
+            RingState::Unknown(value) => value,
+        }
+    }
+}
+
+/// MRM/MRA priority as carried in Test and TopologyChange TLVs; lower values win.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManagerPriority(pub u16);
+
+impl ManagerPriority {
+    pub const HIGHEST: ManagerPriority = ManagerPriority(0x0000);
+    /// Default priority of a ring manager (MRM).
+    pub const DEFAULT: ManagerPriority = ManagerPriority(0x8000);
+    /// Default priority of a ring manager in auto-manager (MRA) mode.
+    pub const AUTO_MANAGER_DEFAULT: ManagerPriority = ManagerPriority(0xa000);
+    pub const LOWEST: ManagerPriority = ManagerPriority(0xffff);
+
+    pub fn is_default(&self) -> bool {
+        *self == ManagerPriority::DEFAULT
+    }
+
+    /// Whether the value lies in the 0x9000..=0xffff range reserved for MRA priorities.
+    pub fn is_auto_manager(&self) -> bool {
+        self.0 >= 0x9000
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRPTestData {
     pub prio: u16,
     pub sa: MacAddress,
-    pub port_role: u16,
-    pub ring_state: u16,
+    pub port_role: PortRole,
+    pub ring_state: RingState,
     pub transition: u16,
     pub timestamp: u32,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRPCommonData {
     pub sequence_id: u16,
     pub domain_uuid: Uuid,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPTopologyChangeData {
+    pub prio: u16,
+    pub sa: MacAddress,
+    pub interval: u16,
+}
+
+/// Payload shared by MRP_LinkDown and MRP_LinkUp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPLinkData {
+    pub sa: MacAddress,
+    pub port_role: PortRole,
+    pub interval: u16,
+    pub blocked: u16,
+}
+
+/// MRP_InTest, sent by an interconnection manager (MIM) around the interconnection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPInTestData {
+    pub in_id: u16,
+    pub sa: MacAddress,
+    pub port_role: PortRole,
+    /// Open or closed state of the interconnection.
+    pub in_state: RingState,
+    pub transition: u16,
+    pub timestamp: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPInTopologyChangeData {
+    pub sa: MacAddress,
+    pub in_id: u16,
+    pub interval: u16,
+}
+
+/// Payload shared by MRP_InLinkDown and MRP_InLinkUp.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPInLinkData {
+    pub sa: MacAddress,
+    pub port_role: PortRole,
+    pub in_id: u16,
+    pub interval: u16,
+    pub link_info: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPInLinkStatusPollData {
+    pub sa: MacAddress,
+    pub port_role: PortRole,
+    pub in_id: u16,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MRPOptionData {
     pub manufacturer_oui: [u8; 3],
-    pub ed1_type: u8,
-    pub ed1_manufacturer_data: u16,
+    pub sub_tlvs: Vec<MRPSubTlv>,
 }
 
-#[derive(Debug, PartialEq)]
+/// OUI of Siemens, whose MRP_Option TLVs start with an [`MRPSubTlv::Ed1`] header.
+pub const SIEMENS_OUI: [u8; 3] = [0x08, 0x00, 0x06];
+
+/// Vendors of MRP-capable equipment, by IEEE-registered OUI.
+const MANUFACTURERS: [([u8; 3], &str); 13] = [
+    (SIEMENS_OUI, "Siemens"),
+    ([0x00, 0x0e, 0x8c], "Siemens"),
+    ([0x00, 0x1b, 0x1b], "Siemens"),
+    ([0x00, 0x80, 0x63], "Hirschmann"),
+    ([0x00, 0xa0, 0x45], "Phoenix Contact"),
+    ([0x00, 0x30, 0xde], "WAGO"),
+    ([0x00, 0x00, 0xbc], "Rockwell Automation"),
+    ([0x00, 0x1d, 0x9c], "Rockwell Automation"),
+    ([0x00, 0x07, 0x7c], "Westermo"),
+    ([0x00, 0x90, 0xe8], "Moxa"),
+    ([0x00, 0x01, 0x05], "Beckhoff"),
+    ([0x00, 0x02, 0xa2], "Hilscher"),
+    ([0x00, 0x0e, 0xcf], "PROFIBUS Nutzerorganisation"),
+];
+
+/// One entry of an MRP_Option TLV after the OUI.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MRPSubTlv {
+    /// The Ed1Type and ManufacturerData fields leading a [`SIEMENS_OUI`] option; not a sub-TLV
+    /// itself, so it has no type or length byte on the wire.
+    Ed1 {
+        ed1_type: u8,
+        manufacturer_data: u16,
+    },
+    /// MRP_TestMgrNAck (sub-type 0x01).
+    TestMgrNAck(MRPManagerData),
+    /// MRP_TestPropagate (sub-type 0x02).
+    TestPropagate(MRPManagerData),
+    /// MRP_AutoMgr (sub-type 0x03), which carries no value.
+    AutoMgr,
+    /// A sub-TLV this crate does not decode, kept as its raw value bytes.
+    Raw { sub_type: u8, value: Vec<u8> },
+}
+
+/// Value of the MRP_TestMgrNAck and MRP_TestPropagate sub-TLVs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MRPManagerData {
+    pub prio: u16,
+    pub sa: MacAddress,
+    pub other_prio: u16,
+    pub other_sa: MacAddress,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
 pub struct MacAddress([u8; 6]);
 
-impl From<&[u8]> for MacAddress {
-    fn from(bytes: &[u8]) -> Self {
-        let mut addr = [0u8; 6];
-        addr.copy_from_slice(bytes);
-        MacAddress(addr)
-    }
+/// Layer-2 control protocols with destination addresses reserved by the IEEE or IEC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KnownProtocol {
+    Mrp,
+    SpanningTree,
+    Lldp,
+    Rstp,
 }
 
-impl fmt::Display for MacAddress {
+/// Inclusive destination address ranges reserved for each [`KnownProtocol`].
+const RESERVED_ADDRESSES: [(KnownProtocol, [u8; 6], [u8; 6]); 6] = [
+    (
+        KnownProtocol::Mrp,
+        [0x01, 0x15, 0x4e, 0x00, 0x00, 0x01],
+        [0x01, 0x15, 0x4e, 0x00, 0x00, 0x04],
+    ),
+    (
+        KnownProtocol::SpanningTree,
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+    ),
+    (
+        KnownProtocol::Rstp,
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+    ),
+    // Nearest customer bridge, nearest non-TPMR bridge and nearest bridge (IEEE 802.1AB).
+    (
+        KnownProtocol::Lldp,
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x00],
+    ),
+    (
+        KnownProtocol::Lldp,
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x03],
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x03],
+    ),
+    (
+        KnownProtocol::Lldp,
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e],
+        [0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e],
+    ),
+];
+
+/// Returned when a string is not a MAC address in "aa:bb:cc:dd:ee:ff" or "aa-bb-cc-dd-ee-ff" form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MacAddressParseError;
+
+impl fmt::Display for MacAddressParseError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
-            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
-        )
+        write!(f, "invalid MAC address")
     }
 }
 
-impl fmt::Display for MRPData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "MRP Version: {:#06x}\n", self.version)?;
-        for header in &self.tlv_headers {
-            write!(f, "{}", header)?;
-        }
-        Ok(())
+#[cfg(feature = "std")]
+impl std::error::Error for MacAddressParseError {}
+
+impl core::str::FromStr for MacAddress {
+    type Err = MacAddressParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let separator = if s.contains('-') { '-' } else { ':' };
+        MacAddress::parse_separated(s, separator)
     }
 }
 
-impl fmt::Display for MRPTLVHeader {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "  TLV Type: {:#04x}, Length: {}\n  Data:\n{}",
-            self.tlv_type, self.length, self.data
+impl MacAddress {
+    fn parse_separated(s: &str, separator: char) -> Result<Self, MacAddressParseError> {
+        let mut octets = [0u8; 6];
+        let mut parts = s.split(separator);
+        for octet in &mut octets {
+            *octet = parts
+                .next()
+                .filter(|part| part.len() == 2 && part.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|part| u8::from_str_radix(part, 16).ok())
+                .ok_or(MacAddressParseError)?;
+        }
+        if parts.next().is_some() {
+            return Err(MacAddressParseError);
+        }
+        Ok(MacAddress(octets))
+    }
+
+    /// Octets in transmission order, the same as `MacAddress::from(bytes)`.
+    pub fn from_bytes_be(bytes: [u8; 6]) -> MacAddress {
+        MacAddress(bytes)
+    }
+
+    /// Octets stored last-first, as some little-endian NIC drivers keep them.
+    pub fn from_bytes_le(mut bytes: [u8; 6]) -> MacAddress {
+        bytes.reverse();
+        MacAddress(bytes)
+    }
+
+    pub fn octets(&self) -> [u8; 6] {
+        self.0
+    }
+
+    /// The organizationally unique identifier in the first three octets.
+    pub fn oui(&self) -> [u8; 3] {
+        [self.0[0], self.0[1], self.0[2]]
+    }
+
+    /// Bitwise AND of each octet with the matching octet of `mask`.
+    pub fn apply_mask(&self, mask: &MacAddress) -> MacAddress {
+        MacAddress(core::array::from_fn(|i| self.0[i] & mask.0[i]))
+    }
+
+    /// Whether the individual/group bit is set, i.e. this is a multicast or broadcast address.
+    pub fn is_multicast(&self) -> bool {
+        self.0[0] & 0x01 != 0
+    }
+
+    /// Whether this is one of the MRP multicast groups 01:15:4e:00:00:01 to 01:15:4e:00:00:04.
+    pub fn is_mrp_multicast(&self) -> bool {
+        self.0[..5] == [0x01, 0x15, 0x4e, 0x00, 0x00] && (0x01..=0x04).contains(&self.0[5])
+    }
+
+    /// Whether this is a destination address reserved for `protocol`.
+    pub fn is_reserved_for_protocol(&self, protocol: KnownProtocol) -> bool {
+        RESERVED_ADDRESSES
+            .iter()
+            .any(|(reserved_for, first, last)| {
+                *reserved_for == protocol && (*first..=*last).contains(&self.0)
+            })
+    }
+
+    /// The address as a big-endian integer in the low 48 bits.
+    pub fn to_u64(&self) -> u64 {
+        let mut bytes = [0u8; 8];
+        bytes[2..].copy_from_slice(&self.0);
+        u64::from_be_bytes(bytes)
+    }
+
+    /// The inverse of [`to_u64`](MacAddress::to_u64); `None` if any of the upper 16 bits are set.
+    pub fn from_integer(v: u64) -> Option<MacAddress> {
+        if v >> 48 != 0 {
+            return None;
+        }
+        let bytes = v.to_be_bytes();
+        Some(MacAddress([
+            bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+        ]))
+    }
+
+    /// The address in Cisco's dotted form, e.g. "000e.8ce0.2f22".
+    pub fn to_cisco_notation(&self) -> String {
+        alloc::format!(
+            "{:02x}{:02x}.{:02x}{:02x}.{:02x}{:02x}",
+            self.0[0],
+            self.0[1],
+            self.0[2],
+            self.0[3],
+            self.0[4],
+            self.0[5]
         )
     }
-}
 
-impl fmt::Display for MRPTLVData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            MRPTLVData::MRPTest(data) => write!(f, "{}", data),
-            MRPTLVData::MRPCommon(data) => write!(f, "{}", data),
-            MRPTLVData::MRPOption(data) => write!(f, "{}", data),
-            MRPTLVData::MRPEnd => write!(f, "  End of MRP Data\n"),
+    /// The address as Windows tools print it, e.g. "00-0E-8C-E0-2F-22".
+    pub fn to_windows_notation(&self) -> String {
+        alloc::format!(
+            "{:02X}-{:02X}-{:02X}-{:02X}-{:02X}-{:02X}",
+            self.0[0],
+            self.0[1],
+            self.0[2],
+            self.0[3],
+            self.0[4],
+            self.0[5]
+        )
+    }
+
+    /// The six lowercase octets joined by `sep`, e.g. "00-0e-8c-e0-2f-22" for `'-'`.
+    pub fn format_with_separator(&self, sep: char) -> String {
+        use core::fmt::Write;
+
+        let mut text = String::with_capacity(12 + 5 * sep.len_utf8());
+        for (i, octet) in self.0.iter().enumerate() {
+            if i > 0 {
+                text.push(sep);
+            }
+            let _ = write!(text, "{:02x}", octet);
+        }
+        text
+    }
+
+    /// The address as 12 lowercase hex digits with no separator, e.g. "000e8ce02f22".
+    pub fn format_plain(&self) -> String {
+        alloc::format!("{:012x}", self.to_u64())
+    }
+
+    /// Parses six hyphen-separated octets, in either case.
+    pub fn from_windows_notation(s: &str) -> Result<MacAddress, MacAddressParseError> {
+        MacAddress::parse_separated(s, '-')
+    }
+
+    /// Parses three dot-separated groups of four hex digits, in either case.
+    pub fn from_cisco_notation(s: &str) -> Result<MacAddress, MacAddressParseError> {
+        let mut octets = [0u8; 6];
+        let mut groups = s.split('.');
+        for pair in octets.chunks_exact_mut(2) {
+            let group = groups
+                .next()
+                .filter(|group| group.len() == 4 && group.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|group| u16::from_str_radix(group, 16).ok())
+                .ok_or(MacAddressParseError)?;
+            pair.copy_from_slice(&group.to_be_bytes());
+        }
+        if groups.next().is_some() {
+            return Err(MacAddressParseError);
+        }
+        Ok(MacAddress(octets))
+    }
+
+    /// Parses any of the three common notations: "00:0e:8c:e0:2f:22", "00-0E-8C-E0-2F-22"
+    /// or "000e.8ce0.2f22", in either case.
+    pub fn try_from_str(s: &str) -> Result<MacAddress, MacAddressParseError> {
+        if s.contains('.') {
+            MacAddress::from_cisco_notation(s)
+        } else {
+            s.parse()
         }
     }
 }
 
-impl fmt::Display for MRPTestData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "    MRP Test Data:\n      Prio: {:#06x}\n      SA: {}\n      Port Role: {:#06x}\n      Ring State: {:#06x}\n      Transition: {:#06x}\n      Timestamp: {:#010x}\n",
-            self.prio, self.sa, self.port_role, self.ring_state, self.transition, self.timestamp
-        )
+impl From<[u8; 6]> for MacAddress {
+    fn from(bytes: [u8; 6]) -> Self {
+        MacAddress(bytes)
     }
 }
 
-impl fmt::Display for MRPCommonData {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "    MRP Common Data:\n      Sequence ID: {:#06x}\n      Domain UUID: {}\n",
-            self.sequence_id, self.domain_uuid
-        )
+impl TryFrom<&[u8]> for MacAddress {
+    type Error = core::array::TryFromSliceError;
+
+    /// Fails unless `bytes` is exactly 6 bytes long.
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        <[u8; 6]>::try_from(bytes).map(MacAddress)
     }
 }
 
-impl fmt::Display for MRPOptionData {
+impl fmt::Display for MacAddress {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "    MRP Option Data:\n      Manufacturer OUI: {:02x}:{:02x}:{:02x}\n      Ed1 Type: {:#04x}\n      Ed1 Manufacturer Data: {:#06x}\n",
-            self.manufacturer_oui[0],
-            self.manufacturer_oui[1],
-            self.manufacturer_oui[2],
-            self.ed1_type,
-            self.ed1_manufacturer_data
+            "{:02x}:{:02x}:{:02x}:{:02x}:{:02x}:{:02x}",
+            self.0[0], self.0[1], self.0[2], self.0[3], self.0[4], self.0[5]
         )
     }
 }
 
-pub fn parse_mac_address(data: &[u8]) -> MacAddress {
-    MacAddress::from(data)
-}
+impl MRPData {
+    pub fn new(version: u16) -> Self {
+        MRPData {
+            version,
+            tlv_headers: Vec::new(),
+        }
+    }
 
-pub fn parse_u16(data: &[u8]) -> u16 {
-    u16::from_be_bytes([data[0], data[1]])
-}
+    /// An empty PDU of [`MRP_VERSION`], the only version IEC 62439-2 defines.
+    pub fn with_version_1() -> Self {
+        MRPData::new(MRP_VERSION)
+    }
 
-pub fn parse_u32(data: &[u8]) -> u32 {
-    u32::from_be_bytes([data[0], data[1], data[2], data[3]])
-}
+    /// A complete MRP_Test PDU in wire order: Test, Common, the Option if given, then End.
+    ///
+    /// Fails only if the Option's value is too long for its length byte.
+    pub fn with_all_tlvs(
+        version: u16,
+        common: MRPCommonData,
+        test: MRPTestData,
+        option: Option<MRPOptionData>,
+    ) -> Result<Self, EncodeError> {
+        let mut mrp_data = MRPData::new(version);
+        mrp_data.push(MRPTLVData::MRPTest(test))?;
+        mrp_data.push(MRPTLVData::MRPCommon(common))?;
+        if let Some(option) = option {
+            mrp_data.push(MRPTLVData::MRPOption(option))?;
+        }
+        mrp_data.push(MRPTLVData::MRPEnd)?;
+        Ok(mrp_data)
+    }
 
-pub fn parse_mrp_data(data: &[u8]) -> Option<MRPData> {
-    if data.len() < 2 {
-        //print(!("Insufficient data for version");
-        return None;
+    /// Appends a TLV, filling in its type and length from `data`.
+    ///
+    /// Fails with [`EncodeError::ValueTooLong`] if the value does not fit a length byte.
+    pub fn push(&mut self, data: MRPTLVData) -> Result<(), EncodeError> {
+        self.tlv_headers.push(MRPTLVHeader::new(data)?);
+        Ok(())
     }
 
-    let version = parse_u16(&data[0..2]);
-    //print(!("Parsed version: {:#06x}", version);
-    let mut offset = 2;
-    let mut tlv_headers = Vec::new();
+    /// Fails with [`EncodeError::ValueTooLong`] if any TLV value is over 255 bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut buf = self.version.to_be_bytes().to_vec();
+        buf.extend_from_slice(&self.serialize_tlvs_only()?);
+        Ok(buf)
+    }
 
-    while offset < data.len() {
-        if offset + 2 > data.len() {
-            //print(!("Insufficient data for TLV header");
-            return None;
+    /// Encodes into a fixed-size array, zero-filling whatever the PDU does not use.
+    pub fn to_fixed_bytes<const N: usize>(&self) -> Result<[u8; N], EncodeError> {
+        let bytes = self.to_bytes()?;
+        let mut buf = [0u8; N];
+        buf.get_mut(..bytes.len())
+            .ok_or(EncodeError::BufferTooSmall {
+                needed: bytes.len(),
+                available: N,
+            })?
+            .copy_from_slice(&bytes);
+        Ok(buf)
+    }
+
+    /// Encodes the TLV sequence without the leading version; the inverse of
+    /// [`parse_mrp_tlvs_only`].
+    pub fn serialize_tlvs_only(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut buf = Vec::new();
+        for header in &self.tlv_headers {
+            header.write_bytes(&mut buf)?;
         }
+        Ok(buf)
+    }
 
-        let tlv_type = data[offset];
-        let length = data[offset + 1] as usize;
+    /// An [`EncodeError`] is reported as [`std::io::ErrorKind::InvalidInput`] and nothing is
+    /// written.
+    #[cfg(feature = "std")]
+    pub fn write_to<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        let bytes = self
+            .to_bytes()
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err))?;
+        writer.write_all(&bytes)
+    }
 
-        if offset + 2 + length > data.len() {
-            //print(!("Insufficient data for TLV value");
-            return None;
-        }
+    /// The [`Display`](fmt::Display) output with every SA and domain UUID replaced by its
+    /// pseudonym from `redactor`; `self` is left untouched.
+    pub fn display_redacted(&self, redactor: &mut redact::Redactor) -> impl fmt::Display {
+        redactor.redacted(self)
+    }
 
-        let tlv_data = &data[offset + 2..offset + 2 + length];
-        //print(!("Parsing TLV type: {:#04x}, length: {}", tlv_type, length);
+    /// Encodes the parsed structure as MessagePack.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, rmp_serde::encode::Error> {
+        rmp_serde::to_vec(self)
+    }
 
-        let tlv_header = match tlv_type {
-            0x02 => {
-                //print(!("Parsing MRPTest TLV");
-                MRPTLVHeader {
-                    tlv_type,
-                    length: length as u8,
-                    data: MRPTLVData::MRPTest(MRPTestData {
-                        prio: parse_u16(&tlv_data[0..2]),
-                        sa: parse_mac_address(&tlv_data[2..8]),
-                        port_role: parse_u16(&tlv_data[8..10]),
-                        ring_state: parse_u16(&tlv_data[10..12]),
-                        transition: parse_u16(&tlv_data[12..14]),
-                        timestamp: parse_u32(&tlv_data[14..18]),
-                    }),
-                }
-            }
-            0x01 => {
-                //print(!("Parsing MRPCommon TLV");
-                MRPTLVHeader {
-                    tlv_type,
-                    length: length as u8,
-                    data: MRPTLVData::MRPCommon(MRPCommonData {
-                        sequence_id: parse_u16(&tlv_data[0..2]),
-                        domain_uuid: Uuid::from_slice(&tlv_data[2..18]).ok()?,
-                    }),
-                }
-            }
-            0x7f => {
-                //print(!("Parsing MRPOption TLV");
-                MRPTLVHeader {
-                    tlv_type,
-                    length: length as u8,
-                    data: MRPTLVData::MRPOption(MRPOptionData {
-                        manufacturer_oui: [tlv_data[0], tlv_data[1], tlv_data[2]],
-                        ed1_type: tlv_data[3],
-                        ed1_manufacturer_data: parse_u16(&tlv_data[4..6]),
-                    }),
-                }
-            }
-            0x00 => {
-                //print(!("Parsing MRPEnd TLV");
-                MRPTLVHeader {
-                    tlv_type,
-                    length: 0,
-                    data: MRPTLVData::MRPEnd,
-                }
-            }
-            _ => {
-                //print(!("Unknown TLV type");
-                return None;
-            }
-        };
-        tlv_headers.push(tlv_header);
-        offset += 2 + length;
-        //print(!("Offset updated to: {}", offset);
+    /// Encodes the parsed structure as a single line of JSON, for structured logging.
+    #[cfg(feature = "json")]
+    pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
     }
 
-    //print(!("Parsed MRPData with {} TLV headers", tlv_headers.len());
+    /// Like [`to_json_string`](MRPData::to_json_string), with every SA and domain UUID replaced
+    /// by its pseudonym from `redactor`; `self` is left untouched.
+    #[cfg(feature = "json")]
+    pub fn to_json_string_redacted(
+        &self,
+        redactor: &mut redact::Redactor,
+    ) -> Result<String, serde_json::Error> {
+        redactor.redacted(self).to_json_string()
+    }
 
-    Some(MRPData {
-        version,
-        tlv_headers,
-    })
-}
+    /// Converts the parsed structure into a JSON tree with the same shape as
+    /// [`to_json_string`](MRPData::to_json_string), without formatting it as text first.
+    #[cfg(feature = "json")]
+    pub fn into_json_value(self) -> serde_json::Value {
+        serde_json::to_value(self).expect("MRPData serializes to JSON infallibly")
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(bytes: &[u8]) -> Result<Self, rmp_serde::decode::Error> {
+        rmp_serde::from_slice(bytes)
+    }
 
-    #[test]
-    fn test_parse_mac_address() {
-        let mac_bytes = vec![0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22];
-        let mac = parse_mac_address(&mac_bytes);
-        assert_eq!(mac, MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]));
+    pub fn iter(&self) -> core::slice::Iter<'_, MRPTLVHeader> {
+        self.tlv_headers.iter()
     }
 
-    #[test]
-    fn test_parse_u16() {
-        let bytes = vec![0x12, 0x34];
-        let value = parse_u16(&bytes);
-        assert_eq!(value, 0x1234);
+    /// The TLV right after the version, normally the frame TLV.
+    pub fn first_tlv(&self) -> Option<&MRPTLVHeader> {
+        self.tlv_headers.first()
     }
 
-    #[test]
-    fn test_parse_u32() {
-        let bytes = vec![0x12, 0x34, 0x56, 0x78];
-        let value = parse_u32(&bytes);
-        assert_eq!(value, 0x12345678);
+    /// The final TLV, normally MRP_End.
+    pub fn last_tlv(&self) -> Option<&MRPTLVHeader> {
+        self.tlv_headers.last()
     }
 
-    #[test]
-    fn test_parse_mrp_data() {
-        let payload: Vec<u8> = vec![
-            0x00, 0x01, 0x02, 0x12, 0xa0, 0x00, 0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22,
-            0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x19, 0xfa, 0x3f, 0xd4, 0x01, 0x12,
-            0x05, 0x7e, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb,
-            0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6, 0x7f, 0x06, 0x08, 0x00, 0x06, 0x00,
-            0x00, 0x00, 0x00, 0x00,
-        ];
+    /// Whether both PDUs match field for field except for the timestamp of their MRP_Test or
+    /// MRP_InTest TLVs, e.g. a Test frame and its retransmission.
+    pub fn eq_ignoring_timestamp(&self, other: &MRPData) -> bool {
+        self.eq_tlvs_by(other, |a, b| match (a, b) {
+            (MRPTLVData::MRPTest(a), MRPTLVData::MRPTest(b)) => {
+                MRPTestData {
+                    timestamp: b.timestamp,
+                    ..*a
+                } == *b
+            }
+            (MRPTLVData::MRPInTest(a), MRPTLVData::MRPInTest(b)) => {
+                MRPInTestData {
+                    timestamp: b.timestamp,
+                    ..*a
+                } == *b
+            }
+            _ => a == b,
+        })
+    }
 
-        let mrp_data = parse_mrp_data(&payload).expect("Failed to parse MRP data");
-        //print(!("{}", mrp_data);
-        
-        // Assertions pour vérifier que les données sont correctes
-        assert_eq!(mrp_data.version, 0x0001);
-        assert_eq!(mrp_data.tlv_headers.len(), 4);
+    /// Whether both PDUs match field for field except for their MRP_Common sequence IDs, e.g.
+    /// the same periodic frame sent again later.
+    pub fn eq_ignoring_sequence_id(&self, other: &MRPData) -> bool {
+        self.eq_tlvs_by(other, |a, b| match (a, b) {
+            (MRPTLVData::MRPCommon(a), MRPTLVData::MRPCommon(b)) => a.domain_uuid == b.domain_uuid,
+            _ => a == b,
+        })
+    }
 
-        if let MRPTLVData::MRPTest(data) = &mrp_data.tlv_headers[0].data {
-            assert_eq!(data.prio, 0xa000);
-            assert_eq!(data.sa.to_string(), "00:0e:8c:e0:2f:22");
-            assert_eq!(data.port_role, 0x0000);
-            assert_eq!(data.ring_state, 0x0000);
-            assert_eq!(data.transition, 0x0001);
-            assert_eq!(data.timestamp, 0x19fa3fd4);
-        } else {
-            panic!("Expected MRPTest data");
+    /// Compares version and TLV headers, deciding whether the TLV data match with `eq`.
+    fn eq_tlvs_by(&self, other: &MRPData, eq: impl Fn(&MRPTLVData, &MRPTLVData) -> bool) -> bool {
+        self.version == other.version
+            && self.tlv_headers.len() == other.tlv_headers.len()
+            && self.iter().zip(other.iter()).all(|(a, b)| {
+                a.tlv_type == b.tlv_type && a.length == b.length && eq(&a.data, &b.data)
+            })
+    }
+
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, MRPTLVHeader> {
+        self.tlv_headers.iter_mut()
+    }
+
+    /// Sets every TLV's `length` to the size of the data it carries. Nothing is changed if a
+    /// value is too long for its length byte.
+    pub fn fix_lengths(&mut self) -> Result<(), EncodeError> {
+        let lengths = self
+            .iter()
+            .map(|header| header.data.length_byte())
+            .collect::<Result<Vec<u8>, EncodeError>>()?;
+        for (header, length) in self.iter_mut().zip(lengths) {
+            header.length = length;
         }
+        Ok(())
+    }
 
-        if let MRPTLVData::MRPCommon(data) = &mrp_data.tlv_headers[1].data {
-            assert_eq!(data.sequence_id, 0x057e);
-            assert_eq!(data.domain_uuid, Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap());
-        } else {
-            panic!("Expected MRPCommon data");
+    /// A copy in the TLV order IEC 62439-2 sends: the frame TLV(s), MRP_Common, MRP_Option and
+    /// a single MRP_End. Unknown TLVs and any other End TLVs are dropped and type and length
+    /// bytes are recomputed. TLVs of the same kind are ordered by type byte, then by value
+    /// bytes, so the result does not depend on the input order.
+    ///
+    /// The frame TLV comes before MRP_Common, not after it, because a PDU in any other order
+    /// fails [`validate`](MRPData::validate). The copy must have consistent length bytes, so
+    /// this fails with [`EncodeError::ValueTooLong`] rather than returning one that can't be
+    /// encoded.
+    pub fn to_canonical_form(&self) -> Result<MRPData, EncodeError> {
+        let mut tlv_headers = self
+            .iter()
+            .filter(|header| {
+                !matches!(header.data, MRPTLVData::MRPEnd | MRPTLVData::Unknown { .. })
+            })
+            .map(|header| MRPTLVHeader::new(header.data.clone()))
+            .collect::<Result<Vec<MRPTLVHeader>, EncodeError>>()?;
+        tlv_headers.sort_by_cached_key(|header| {
+            let mut value = Vec::new();
+            header.data.write_payload(&mut value);
+            (wire_rank(&header.data), header.tlv_type, value)
+        });
+        tlv_headers.push(MRPTLVHeader::new(MRPTLVData::MRPEnd)?);
+        Ok(MRPData {
+            version: self.version,
+            tlv_headers,
+        })
+    }
+
+    /// Replaces the first TLV of the same kind as `data`, returning what it held. If there is
+    /// none, `data` is inserted where IEC 62439-2 places it, after any kinds sent before it.
+    ///
+    /// Fails, leaving the PDU unchanged, if `data` is too long for its length byte.
+    pub fn replace_tlv(&mut self, data: MRPTLVData) -> Result<Option<MRPTLVData>, EncodeError> {
+        let header = MRPTLVHeader::new(data)?;
+        if let Some(old) = self
+            .iter_mut()
+            .find(|old| old.data.is_equal_type(&header.data))
+        {
+            return Ok(Some(core::mem::replace(old, header).data));
         }
+        let rank = wire_rank(&header.data);
+        let index = self
+            .iter()
+            .position(|old| wire_rank(&old.data) > rank)
+            .unwrap_or(self.tlv_headers.len());
+        self.tlv_headers.insert(index, header);
+        Ok(None)
+    }
 
-        if let MRPTLVData::MRPOption(data) = &mrp_data.tlv_headers[2].data {
-            assert_eq!(data.manufacturer_oui, [0x08, 0x00, 0x06]);
-            assert_eq!(data.ed1_type, 0x00);
-            assert_eq!(data.ed1_manufacturer_data, 0x0000);
+    pub fn replace_common_tlv(&mut self, common: MRPCommonData) -> Option<MRPCommonData> {
+        match self.replace_tlv(MRPTLVData::MRPCommon(common)) {
+            Ok(Some(MRPTLVData::MRPCommon(old))) => Some(old),
+            _ => None,
+        }
+    }
+
+    pub fn replace_test_tlv(&mut self, test: MRPTestData) -> Option<MRPTestData> {
+        match self.replace_tlv(MRPTLVData::MRPTest(test)) {
+            Ok(Some(MRPTLVData::MRPTest(old))) => Some(old),
+            _ => None,
+        }
+    }
+
+    /// Replaces the first MRP_Option TLV; any others are left alone.
+    pub fn replace_option_tlv(
+        &mut self,
+        option: MRPOptionData,
+    ) -> Result<Option<MRPOptionData>, EncodeError> {
+        match self.replace_tlv(MRPTLVData::MRPOption(option))? {
+            Some(MRPTLVData::MRPOption(old)) => Ok(Some(old)),
+            _ => Ok(None),
+        }
+    }
+
+    /// Sets every TLV's `tlv_type` to the type of the data it carries.
+    pub fn fix_types(&mut self) {
+        for header in self.iter_mut() {
+            header.tlv_type = header.data.type_byte();
+        }
+    }
+
+    pub fn apply_sa_remapping<F: Fn(&MacAddress) -> MacAddress>(&mut self, f: F) {
+        for header in &mut self.tlv_headers {
+            if let MRPTLVData::MRPTest(data) = &mut header.data {
+                data.sa = f(&data.sa);
+            }
+        }
+    }
+
+    /// Shifts the MRP_Common sequence IDs of a captured run so that it starts at `start_seq`.
+    ///
+    /// The first sequence ID found in `frames` becomes `start_seq` and every other Common
+    /// TLV is moved by the same amount, wrapping at u16, so gaps and order are preserved.
+    pub fn apply_replay_offset(frames: &mut [MRPData], start_seq: u16) {
+        let Some(first) = frames.iter().find_map(MRPData::sequence_id) else {
+            return;
+        };
+        let delta = start_seq.wrapping_sub(first);
+        for header in frames.iter_mut().flat_map(|mrp_data| mrp_data.iter_mut()) {
+            if let MRPTLVData::MRPCommon(common) = &mut header.data {
+                *common = &*common + delta;
+            }
+        }
+    }
+
+    /// The first MRP_Test TLV, if any.
+    pub fn test(&self) -> Option<&MRPTestData> {
+        self.iter().find_map(|header| match &header.data {
+            MRPTLVData::MRPTest(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    /// The first MRP_Common TLV, if any; see [`commons`](MRPData::commons) for the rest.
+    pub fn common(&self) -> Option<&MRPCommonData> {
+        self.commons().next()
+    }
+
+    pub fn commons(&self) -> impl Iterator<Item = &MRPCommonData> {
+        self.iter().filter_map(|header| match &header.data {
+            MRPTLVData::MRPCommon(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    pub fn options(&self) -> impl Iterator<Item = &MRPOptionData> {
+        self.iter().filter_map(|header| match &header.data {
+            MRPTLVData::MRPOption(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    pub fn sequence_id(&self) -> Option<u16> {
+        self.common().map(|common| common.sequence_id)
+    }
+
+    pub fn domain_uuid(&self) -> Option<Uuid> {
+        self.common().map(|common| common.domain_uuid)
+    }
+
+    /// A one-line description for log output, e.g.
+    /// "MRP v1 Test sa=00:0e:8c:e0:2f:22 ring=open prio=0xa000 seq=0x057e uuid=c3d687fe-...".
+    pub fn summary(&self) -> String {
+        use core::fmt::Write;
+
+        let mut line = alloc::format!("MRP v{}", self.version);
+        let frame = self
+            .iter()
+            .map(|header| &header.data)
+            .find(|data| validate::is_frame_tlv(data));
+        let _ = match frame {
+            Some(MRPTLVData::MRPTest(data)) => write!(
+                line,
+                " Test sa={} ring={} prio={:#06x}",
+                data.sa,
+                ring_state_label(data.ring_state),
+                data.prio
+            ),
+            Some(MRPTLVData::MRPTopologyChange(data)) => write!(
+                line,
+                " TopologyChange sa={} prio={:#06x} interval={}",
+                data.sa, data.prio, data.interval
+            ),
+            Some(MRPTLVData::MRPLinkDown(data)) | Some(MRPTLVData::MRPLinkUp(data)) => write!(
+                line,
+                " {} sa={} port={} interval={}",
+                if matches!(frame, Some(MRPTLVData::MRPLinkDown(_))) {
+                    "LinkDown"
+                } else {
+                    "LinkUp"
+                },
+                data.sa,
+                port_role_label(data.port_role),
+                data.interval
+            ),
+            Some(MRPTLVData::MRPInTest(data)) => write!(
+                line,
+                " InTest sa={} in_id={:#06x} in_state={}",
+                data.sa,
+                data.in_id,
+                ring_state_label(data.in_state)
+            ),
+            Some(MRPTLVData::MRPInTopologyChange(data)) => write!(
+                line,
+                " InTopologyChange sa={} in_id={:#06x} interval={}",
+                data.sa, data.in_id, data.interval
+            ),
+            Some(MRPTLVData::MRPInLinkDown(data)) | Some(MRPTLVData::MRPInLinkUp(data)) => write!(
+                line,
+                " {} sa={} port={} in_id={:#06x} interval={}",
+                if matches!(frame, Some(MRPTLVData::MRPInLinkDown(_))) {
+                    "InLinkDown"
+                } else {
+                    "InLinkUp"
+                },
+                data.sa,
+                port_role_label(data.port_role),
+                data.in_id,
+                data.interval
+            ),
+            Some(MRPTLVData::MRPInLinkStatusPoll(data)) => write!(
+                line,
+                " InLinkStatusPoll sa={} port={} in_id={:#06x}",
+                data.sa,
+                port_role_label(data.port_role),
+                data.in_id
+            ),
+            _ => write!(line, " Unknown"),
+        };
+        if let Some(common) = self.common() {
+            let _ = write!(
+                line,
+                " seq={:#06x} uuid={}",
+                common.sequence_id, common.domain_uuid
+            );
+        }
+        line
+    }
+
+    /// Renders the PDU as a Graphviz digraph: the version is the root, each TLV a child of it,
+    /// and the fields shown by the TLV's [`Display`](fmt::Display) output nest below the TLV
+    /// the way that output indents them.
+    pub fn to_dot(&self) -> String {
+        use core::fmt::Write;
+
+        let mut dot = String::from("digraph mrp {\n    node [shape=box];\n");
+        let _ = writeln!(
+            dot,
+            "    pdu [label=\"MRP Version: {:#06x}\"];",
+            self.version
+        );
+        for (i, header) in self.tlv_headers.iter().enumerate() {
+            let tlv = alloc::format!("tlv{}", i);
+            let name = match MrpTlvType::try_from(header.tlv_type) {
+                Ok(tlv_type) => alloc::format!("MRP_{:?}", tlv_type),
+                Err(_) => String::from("Unknown"),
+            };
+            let _ = writeln!(
+                dot,
+                "    {} [label=\"{} ({:#04x})\"];\n    pdu -> {};",
+                tlv, name, header.tlv_type, tlv
+            );
+
+            // The first line is the TLV's own heading, already drawn above.
+            let mut parents: Vec<(usize, String)> = Vec::new();
+            for (j, line) in alloc::format!("{}", header.data)
+                .lines()
+                .skip(1)
+                .enumerate()
+            {
+                let label = line.trim_start();
+                let indent = line.len() - label.len();
+                while parents.last().is_some_and(|(depth, _)| *depth >= indent) {
+                    parents.pop();
+                }
+                let node = alloc::format!("{}_{}", tlv, j);
+                let parent = parents.last().map_or(&tlv, |(_, parent)| parent);
+                let _ = writeln!(
+                    dot,
+                    "    {} [label=\"{}\"];\n    {} -> {};",
+                    node,
+                    label
+                        .trim_end_matches(':')
+                        .replace('\\', "\\\\")
+                        .replace('"', "\\\""),
+                    parent,
+                    node
+                );
+                parents.push((indent, node));
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The encoded PDU as a hex dump that Wireshark's "Import from Hex Dump" reads: a `# MRP`
+    /// comment line, then 16 bytes per line after a 4-digit hex offset.
+    pub fn debug_hex(&self) -> Result<String, EncodeError> {
+        use core::fmt::Write;
+
+        let mut dump = String::from("# MRP\n");
+        for (i, line) in self.to_bytes()?.chunks(16).enumerate() {
+            let _ = write!(dump, "{:04x} ", i * 16);
+            for byte in line {
+                let _ = write!(dump, " {:02x}", byte);
+            }
+            dump.push('\n');
+        }
+        Ok(dump)
+    }
+
+    pub fn frame_kind(&self) -> MrpFrameKind {
+        let first = self
+            .iter()
+            .map(|header| &header.data)
+            .find(|data| !matches!(data, MRPTLVData::MRPCommon(_)));
+        match first {
+            Some(MRPTLVData::MRPTest(_)) => MrpFrameKind::Test,
+            Some(MRPTLVData::MRPTopologyChange(_)) => MrpFrameKind::TopologyChange,
+            Some(MRPTLVData::MRPLinkDown(_)) | Some(MRPTLVData::MRPLinkUp(_)) => {
+                MrpFrameKind::LinkChange
+            }
+            Some(MRPTLVData::MRPInTest(_)) => MrpFrameKind::InTest,
+            Some(MRPTLVData::MRPInTopologyChange(_)) => MrpFrameKind::InTopologyChange,
+            Some(MRPTLVData::MRPInLinkDown(_)) | Some(MRPTLVData::MRPInLinkUp(_)) => {
+                MrpFrameKind::InLinkChange
+            }
+            Some(MRPTLVData::MRPInLinkStatusPoll(_)) => MrpFrameKind::InLinkStatusPoll,
+            _ => MrpFrameKind::Unknown,
+        }
+    }
+
+    /// The first MRP_InTest TLV, if any.
+    pub fn in_test(&self) -> Option<&MRPInTestData> {
+        self.iter().find_map(|header| match &header.data {
+            MRPTLVData::MRPInTest(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    /// The first MRP_InLinkDown or MRP_InLinkUp TLV, if any.
+    pub fn in_link(&self) -> Option<&MRPInLinkData> {
+        self.iter().find_map(|header| match &header.data {
+            MRPTLVData::MRPInLinkDown(data) | MRPTLVData::MRPInLinkUp(data) => Some(data),
+            _ => None,
+        })
+    }
+
+    /// Whether the MRP_Common TLV's domain UUID is one of `domains`.
+    pub fn domain_matches_any(&self, domains: &[Uuid]) -> bool {
+        self.commons()
+            .any(|common| domains.contains(&common.domain_uuid))
+    }
+
+    /// Counts ring-state changes between successive Test frames of the same domain that were
+    /// received at most `window` before the latest frame in `frames`.
+    ///
+    /// Frames are taken in slice order; those without a Test TLV are ignored.
+    #[cfg(feature = "std")]
+    pub fn count_transitions_in_window(
+        frames: &[(std::time::Instant, MRPData)],
+        window: Duration,
+    ) -> usize {
+        let Some(latest) = frames.iter().map(|(at, _)| *at).max() else {
+            return 0;
+        };
+        let mut ring_states = alloc::collections::BTreeMap::new();
+        let mut count = 0;
+        for (at, mrp_data) in frames {
+            let Some(test) = mrp_data.test() else {
+                continue;
+            };
+            let domain_uuid = mrp_data.domain_uuid();
+            let previous = ring_states.insert(domain_uuid, test.ring_state);
+            if previous.is_some_and(|previous| previous != test.ring_state)
+                && latest.duration_since(*at) <= window
+            {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// The ring state reported by the MRP_Test TLV of `frames[frame_index]`; `None` if the
+    /// index is out of bounds or that PDU has no Test TLV.
+    pub fn ring_state_at(frame_index: usize, frames: &[MRPData]) -> Option<RingState> {
+        Some(frames.get(frame_index)?.test()?.ring_state)
+    }
+
+    /// The manager SA carried by the MRP_Test TLV of `frames[frame_index]`; `None` if the
+    /// index is out of bounds or that PDU has no Test TLV.
+    pub fn manager_at(frame_index: usize, frames: &[MRPData]) -> Option<MacAddress> {
+        Some(frames.get(frame_index)?.test()?.sa)
+    }
+
+    /// Scores recently received PDUs from 0.0 (failed) to 1.0 (healthy).
+    ///
+    /// The score is `closed × (1 − loss)`, where:
+    ///
+    /// - `closed` is the fraction of MRP_Test TLVs in `frames` reporting
+    ///   [`RingState::Closed`], or 0 if there are none;
+    /// - `loss` is the fraction of PDUs lost according to the MRP_Common sequence IDs, as
+    ///   [`monitor::SequenceTracker::loss_rate`] counts them per domain over `frames` in order.
+    ///
+    /// A closed ring without losses scores 1.0; a ring that is open throughout scores 0.0 however
+    /// few frames were lost, and each factor scales the score linearly.
+    pub fn compute_ring_health_score(frames: &[MRPData]) -> f64 {
+        let mut tracker = monitor::SequenceTracker::new();
+        let (mut tests, mut closed) = (0u32, 0u32);
+        for mrp_data in frames {
+            if let Some(common) = mrp_data.common() {
+                tracker.observe(common);
+            }
+            if let Some(test) = mrp_data.test() {
+                tests += 1;
+                closed += u32::from(test.ring_state == RingState::Closed);
+            }
+        }
+        if tests == 0 {
+            return 0.0;
+        }
+        f64::from(closed) / f64::from(tests) * (1.0 - tracker.loss_rate())
+    }
+}
+
+/// Position of a TLV kind in a PDU: frame TLVs, MRP_Common, MRP_Option and unknown TLVs, MRP_End.
+fn wire_rank(data: &MRPTLVData) -> u8 {
+    match data {
+        MRPTLVData::MRPCommon(_) => 1,
+        MRPTLVData::MRPOption(_) | MRPTLVData::Unknown { .. } => 2,
+        MRPTLVData::MRPEnd => 3,
+        _ => 0,
+    }
+}
+
+impl MRPTLVHeader {
+    /// Fails with [`EncodeError::ValueTooLong`] if the value does not fit a length byte.
+    pub fn new(data: MRPTLVData) -> Result<Self, EncodeError> {
+        Ok(MRPTLVHeader {
+            tlv_type: data.type_byte(),
+            length: data.length_byte()?,
+            data,
+        })
+    }
+
+    /// Appends type, length and value; the length byte is derived from the data.
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+        let length = self.data.length_byte()?;
+        buf.push(self.tlv_type);
+        buf.push(length);
+        self.data.write_payload(buf);
+        Ok(())
+    }
+
+    /// The stored type and length byte, plus the encoded value.
+    pub fn try_split(&self) -> (u8, u8, Vec<u8>) {
+        let mut payload = Vec::with_capacity(self.data.payload_length());
+        self.data.write_payload(&mut payload);
+        (self.tlv_type, self.length, payload)
+    }
+
+    /// Whether `tlv_type` and `length` agree with the TLV data they describe.
+    pub fn is_consistent(&self) -> bool {
+        self.length as usize == self.data.payload_length() && self.tlv_type == self.data.type_byte()
+    }
+
+    /// The type, length and value bytes of this TLV within the PDU it was parsed from.
+    ///
+    /// Headers do not record where they came from, so `original_buf` (starting with the MRP
+    /// version) is walked TLV by TLV and the first one that decodes to this header is
+    /// returned. `None` if no TLV up to MRP_End or the first framing error matches.
+    pub fn as_slice<'buf>(&self, original_buf: &'buf [u8]) -> Option<&'buf [u8]> {
+        let mut offset = 2;
+        for tlv in borrowed::MrpTlvIter::new(original_buf, 2) {
+            let tlv = tlv.ok()?;
+            let end = offset + 2 + tlv.value.len();
+            if tlv.tlv_type == self.tlv_type
+                && tlv.value.len() == self.length as usize
+                && tlv.data().as_ref() == Ok(&self.data)
+            {
+                return Some(&original_buf[offset..end]);
+            }
+            offset = end;
+        }
+        None
+    }
+}
+
+impl MRPTLVData {
+    /// TLV type byte that identifies this data on the wire.
+    pub fn type_byte(&self) -> u8 {
+        match self {
+            MRPTLVData::MRPTest(_) => 0x02,
+            MRPTLVData::MRPCommon(_) => 0x01,
+            MRPTLVData::MRPTopologyChange(_) => 0x03,
+            MRPTLVData::MRPLinkDown(_) => 0x04,
+            MRPTLVData::MRPLinkUp(_) => 0x05,
+            MRPTLVData::MRPInTest(_) => 0x06,
+            MRPTLVData::MRPInTopologyChange(_) => 0x07,
+            MRPTLVData::MRPInLinkDown(_) => 0x08,
+            MRPTLVData::MRPInLinkUp(_) => 0x09,
+            MRPTLVData::MRPInLinkStatusPoll(_) => 0x0a,
+            MRPTLVData::MRPOption(_) => 0x7f,
+            MRPTLVData::MRPEnd => 0x00,
+            MRPTLVData::Unknown { tlv_type, .. } => *tlv_type,
+        }
+    }
+
+    /// Whether both are the same variant, whatever their field values. Unknown TLVs of
+    /// different type bytes count as the same variant.
+    pub fn is_equal_type(&self, other: &MRPTLVData) -> bool {
+        core::mem::discriminant(self) == core::mem::discriminant(other)
+    }
+
+    /// The length byte for this TLV's value; fails if the value is over 255 bytes or is an
+    /// MRP_Option that would not parse back.
+    pub(crate) fn length_byte(&self) -> Result<u8, EncodeError> {
+        if let MRPTLVData::MRPOption(option) = self {
+            if let Some(index) = option.misplaced_ed1() {
+                return Err(EncodeError::MisplacedEd1 { index });
+            }
+        }
+        let length = self.payload_length();
+        u8::try_from(length).map_err(|_| EncodeError::ValueTooLong {
+            tlv_type: self.type_byte(),
+            length,
+        })
+    }
+
+    /// Number of value bytes this TLV occupies on the wire.
+    pub fn payload_length(&self) -> usize {
+        match self {
+            MRPTLVData::MRPTest(_) => 18,
+            MRPTLVData::MRPCommon(_) => 18,
+            MRPTLVData::MRPTopologyChange(_) => 10,
+            MRPTLVData::MRPLinkDown(_) | MRPTLVData::MRPLinkUp(_) => 12,
+            MRPTLVData::MRPInTest(_) => 18,
+            MRPTLVData::MRPInTopologyChange(_) => 10,
+            MRPTLVData::MRPInLinkDown(_) | MRPTLVData::MRPInLinkUp(_) => 14,
+            MRPTLVData::MRPInLinkStatusPoll(_) => 10,
+            MRPTLVData::MRPOption(data) => data.payload_length(),
+            MRPTLVData::MRPEnd => 0,
+            MRPTLVData::Unknown { raw, .. } => raw.len(),
+        }
+    }
+
+    pub fn write_payload(&self, buf: &mut Vec<u8>) {
+        match self {
+            MRPTLVData::MRPTest(data) => data.write_bytes(buf),
+            MRPTLVData::MRPCommon(data) => data.write_bytes(buf),
+            MRPTLVData::MRPTopologyChange(data) => data.write_bytes(buf),
+            MRPTLVData::MRPLinkDown(data) | MRPTLVData::MRPLinkUp(data) => data.write_bytes(buf),
+            MRPTLVData::MRPInTest(data) => data.write_bytes(buf),
+            MRPTLVData::MRPInTopologyChange(data) => data.write_bytes(buf),
+            MRPTLVData::MRPInLinkDown(data) | MRPTLVData::MRPInLinkUp(data) => {
+                data.write_bytes(buf)
+            }
+            MRPTLVData::MRPInLinkStatusPoll(data) => data.write_bytes(buf),
+            MRPTLVData::MRPOption(data) => data.write_bytes(buf),
+            MRPTLVData::MRPEnd => {}
+            MRPTLVData::Unknown { raw, .. } => buf.extend_from_slice(raw),
+        }
+    }
+}
+
+impl MRPTestData {
+    pub fn new(
+        prio: u16,
+        sa: MacAddress,
+        port_role: PortRole,
+        ring_state: RingState,
+        transition: u16,
+        timestamp: u32,
+    ) -> Self {
+        MRPTestData {
+            prio,
+            sa,
+            port_role,
+            ring_state,
+            transition,
+            timestamp,
+        }
+    }
+
+    /// Reads the 18-byte value of an MRP_Test TLV, without its type and length header.
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader<R: std::io::Read>(r: &mut R) -> Result<Self, MRPParseError> {
+        parse_test_data(&read_tlv_value::<R, 18>(r)?)
+    }
+
+    pub fn priority(&self) -> ManagerPriority {
+        ManagerPriority(self.prio)
+    }
+
+    /// The ring state as it appears on the wire, including unknown values.
+    pub fn ring_state_numeric(&self) -> u16 {
+        self.ring_state.into()
+    }
+
+    /// The port role as it appears on the wire, including unknown values.
+    pub fn port_role_numeric(&self) -> u16 {
+        self.port_role.into()
+    }
+
+    /// Guesses whether the sender is a backup manager: the frame left through its secondary
+    /// port and the ring has changed state at least once.
+    ///
+    /// IEC 62439-2 has no backup-manager field, so this is only a heuristic. A primary manager
+    /// sends Test frames through both ports, so its secondary-port frames match as well once
+    /// `transition` is non-zero; compare [`priority`](MRPTestData::priority) across managers
+    /// when the distinction matters.
+    pub fn is_backup_manager(&self) -> bool {
+        self.port_role == PortRole::Secondary && self.transition > 0
+    }
+
+    /// The sender's millisecond counter; only differences between frames are meaningful.
+    pub fn timestamp_ms(&self) -> u32 {
+        self.timestamp
+    }
+
+    pub fn timestamp_duration(&self) -> Duration {
+        Duration::from_millis(self.timestamp.into())
+    }
+
+    /// How much later this frame was stamped than `other`, allowing for the counter wrapping
+    /// at u32. `None` if `other` is the later one, i.e. the modular difference is 2^31 ms or more.
+    pub fn age_since(&self, other: &MRPTestData) -> Option<Duration> {
+        timestamp_age(self.timestamp, other.timestamp)
+    }
+
+    /// Estimates the timestamp of a frame lost between `before` and `after`, `fraction` of the
+    /// way from one to the other. `after` is taken to be the later frame even if the counter
+    /// wrapped in between. `fraction` is clamped to `[0.0, 1.0]`; NaN counts as 0.
+    pub fn interpolate_timestamp(before: &MRPTestData, after: &MRPTestData, fraction: f64) -> u32 {
+        let fraction = if fraction.is_nan() {
+            0.0
         } else {
-            panic!("Expected MRPOption data");
+            fraction.clamp(0.0, 1.0)
+        };
+        let span = after.timestamp.wrapping_sub(before.timestamp);
+        // Round half up; `f64::round` is not available without std.
+        let offset = (f64::from(span) * fraction + 0.5) as u32;
+        before.timestamp.wrapping_add(offset.min(span))
+    }
+
+    /// Key for ring manager election: the lowest key wins, comparing `prio` first and
+    /// breaking ties with the lowest `sa`, which is exactly the tuple ordering.
+    pub fn as_election_key(&self) -> (u16, MacAddress) {
+        (self.prio, self.sa)
+    }
+
+    /// Whether `other` could be the next Test frame from the same manager: same SA and prio.
+    pub fn is_topology_consistent(&self, other: &MRPTestData) -> bool {
+        self.sa == other.sa && self.prio == other.prio
+    }
+
+    /// How many ring-state changes the manager counted since `other`, allowing for the u16
+    /// counter wrapping.
+    pub fn transition_delta_from(&self, other: &MRPTestData) -> u16 {
+        self.transition.wrapping_sub(other.transition)
+    }
+
+    /// The Test TLV the same manager would send 10 ms later after switching to `new_state`:
+    /// one more transition and the timestamp advanced by 10, both wrapping.
+    pub fn simulate_ring_change(&self, new_state: RingState) -> MRPTestData {
+        MRPTestData {
+            ring_state: new_state,
+            transition: self.transition.wrapping_add(1),
+            timestamp: self.timestamp.wrapping_add(10),
+            ..*self
         }
+    }
 
-        if let MRPTLVData::MRPEnd = &mrp_data.tlv_headers[3].data {
-            // Correct end
+    /// Whether `curr` follows `prev` with a consistent transition count: it must have increased
+    /// if the ring state changed and stayed the same otherwise. The count wraps at u16.
+    pub fn verify_ring_state_consistency(prev: &MRPTestData, curr: &MRPTestData) -> bool {
+        let increase = curr.transition_delta_from(prev);
+        if prev.ring_state == curr.ring_state {
+            increase == 0
         } else {
-            panic!("Expected MRPEnd data");
+            (1..0x8000).contains(&increase)
+        }
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.prio.to_be_bytes());
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&u16::from(self.port_role).to_be_bytes());
+        buf.extend_from_slice(&u16::from(self.ring_state).to_be_bytes());
+        buf.extend_from_slice(&self.transition.to_be_bytes());
+        buf.extend_from_slice(&self.timestamp.to_be_bytes());
+    }
+}
+
+impl MRPCommonData {
+    pub fn new(sequence_id: u16, domain_uuid: Uuid) -> Self {
+        MRPCommonData {
+            sequence_id,
+            domain_uuid,
+        }
+    }
+
+    /// Reads the 18-byte value of an MRP_Common TLV, without its type and length header.
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader<R: std::io::Read>(r: &mut R) -> Result<Self, MRPParseError> {
+        parse_common_data(&read_tlv_value::<R, 18>(r)?)
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sequence_id.to_be_bytes());
+        buf.extend_from_slice(self.domain_uuid.as_bytes());
+    }
+
+    /// The Common TLV of the next PDU in the same domain; shorthand for `self + 1`.
+    pub fn next_sequence_id(&self) -> MRPCommonData {
+        self + 1
+    }
+}
+
+/// `&common + n` is the Common TLV `n` PDUs later: same domain, sequence ID wrapped forward.
+impl core::ops::Add<u16> for &MRPCommonData {
+    type Output = MRPCommonData;
+
+    fn add(self, rhs: u16) -> MRPCommonData {
+        MRPCommonData::new(self.sequence_id.wrapping_add(rhs), self.domain_uuid)
+    }
+}
+
+impl MRPTopologyChangeData {
+    pub fn new(prio: u16, sa: MacAddress, interval: u16) -> Self {
+        MRPTopologyChangeData { prio, sa, interval }
+    }
+
+    pub fn priority(&self) -> ManagerPriority {
+        ManagerPriority(self.prio)
+    }
+
+    /// Time until the receivers flush their FDBs.
+    pub fn interval_ms(&self) -> u16 {
+        self.interval
+    }
+
+    pub fn interval_duration(&self) -> Duration {
+        Duration::from_millis(self.interval.into())
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.prio.to_be_bytes());
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&self.interval.to_be_bytes());
+    }
+}
+
+impl MRPLinkData {
+    pub fn new(sa: MacAddress, port_role: PortRole, interval: u16, blocked: u16) -> Self {
+        MRPLinkData {
+            sa,
+            port_role,
+            interval,
+            blocked,
+        }
+    }
+
+    pub fn interval_ms(&self) -> u16 {
+        self.interval
+    }
+
+    pub fn interval_duration(&self) -> Duration {
+        Duration::from_millis(self.interval.into())
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&u16::from(self.port_role).to_be_bytes());
+        buf.extend_from_slice(&self.interval.to_be_bytes());
+        buf.extend_from_slice(&self.blocked.to_be_bytes());
+    }
+}
+
+impl MRPInTestData {
+    pub fn new(
+        in_id: u16,
+        sa: MacAddress,
+        port_role: PortRole,
+        in_state: RingState,
+        transition: u16,
+        timestamp: u32,
+    ) -> Self {
+        MRPInTestData {
+            in_id,
+            sa,
+            port_role,
+            in_state,
+            transition,
+            timestamp,
+        }
+    }
+
+    pub fn timestamp_ms(&self) -> u32 {
+        self.timestamp
+    }
+
+    pub fn timestamp_duration(&self) -> Duration {
+        Duration::from_millis(self.timestamp.into())
+    }
+
+    /// See [`MRPTestData::age_since`].
+    pub fn age_since(&self, other: &MRPInTestData) -> Option<Duration> {
+        timestamp_age(self.timestamp, other.timestamp)
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.in_id.to_be_bytes());
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&u16::from(self.port_role).to_be_bytes());
+        buf.extend_from_slice(&u16::from(self.in_state).to_be_bytes());
+        buf.extend_from_slice(&self.transition.to_be_bytes());
+        buf.extend_from_slice(&self.timestamp.to_be_bytes());
+    }
+}
+
+impl MRPInTopologyChangeData {
+    pub fn new(sa: MacAddress, in_id: u16, interval: u16) -> Self {
+        MRPInTopologyChangeData {
+            sa,
+            in_id,
+            interval,
+        }
+    }
+
+    pub fn interval_ms(&self) -> u16 {
+        self.interval
+    }
+
+    pub fn interval_duration(&self) -> Duration {
+        Duration::from_millis(self.interval.into())
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&self.in_id.to_be_bytes());
+        buf.extend_from_slice(&self.interval.to_be_bytes());
+    }
+}
+
+impl MRPInLinkData {
+    pub fn new(
+        sa: MacAddress,
+        port_role: PortRole,
+        in_id: u16,
+        interval: u16,
+        link_info: u16,
+    ) -> Self {
+        MRPInLinkData {
+            sa,
+            port_role,
+            in_id,
+            interval,
+            link_info,
+        }
+    }
+
+    pub fn interval_ms(&self) -> u16 {
+        self.interval
+    }
+
+    pub fn interval_duration(&self) -> Duration {
+        Duration::from_millis(self.interval.into())
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&u16::from(self.port_role).to_be_bytes());
+        buf.extend_from_slice(&self.in_id.to_be_bytes());
+        buf.extend_from_slice(&self.interval.to_be_bytes());
+        buf.extend_from_slice(&self.link_info.to_be_bytes());
+    }
+}
+
+impl MRPInLinkStatusPollData {
+    pub fn new(sa: MacAddress, port_role: PortRole, in_id: u16) -> Self {
+        MRPInLinkStatusPollData {
+            sa,
+            port_role,
+            in_id,
+        }
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&u16::from(self.port_role).to_be_bytes());
+        buf.extend_from_slice(&self.in_id.to_be_bytes());
+    }
+}
+
+impl MRPOptionData {
+    pub fn new(manufacturer_oui: [u8; 3], sub_tlvs: Vec<MRPSubTlv>) -> Self {
+        MRPOptionData {
+            manufacturer_oui,
+            sub_tlvs,
+        }
+    }
+
+    /// Reads an MRP_Option TLV value, without its type and length header, up to the end of `r`.
+    ///
+    /// The value is variable-length, so bound `r` to the TLV's length byte with
+    /// [`Read::take`](std::io::Read::take); at most 255 bytes are read either way. The
+    /// Siemens option carrying only Ed1Type and ManufacturerData is 6 bytes.
+    #[cfg(feature = "std")]
+    pub fn decode_from_reader<R: std::io::Read>(r: &mut R) -> Result<Self, MRPParseError> {
+        use std::io::Read;
+
+        let mut value = Vec::new();
+        r.take(u8::MAX.into())
+            .read_to_end(&mut value)
+            .map_err(|err| MRPParseError::Io(err.kind()))?;
+        parse_option_data(&value)
+    }
+
+    /// The vendor owning `manufacturer_oui`, or the OUI itself as `08:00:06` if it is not in
+    /// the built-in table.
+    pub fn manufacturer_name_lookup(&self) -> Cow<'static, str> {
+        let oui = self.manufacturer_oui;
+        match MANUFACTURERS.iter().find(|(known, _)| *known == oui) {
+            Some((_, name)) => Cow::Borrowed(name),
+            None => Cow::Owned(alloc::format!(
+                "{:02x}:{:02x}:{:02x}",
+                oui[0],
+                oui[1],
+                oui[2]
+            )),
+        }
+    }
+
+    /// Index of the first sub-TLV breaking the Ed1 rule, if any.
+    ///
+    /// Ed1 has no sub-TLV header, so a decoder recognizes it by position: the first entry of
+    /// a [`SIEMENS_OUI`] option is always Ed1 and nothing else ever is. Anything else would
+    /// parse back differently.
+    pub fn misplaced_ed1(&self) -> Option<usize> {
+        let siemens = self.manufacturer_oui == SIEMENS_OUI;
+        if siemens && !matches!(self.sub_tlvs.first(), Some(MRPSubTlv::Ed1 { .. })) {
+            return Some(0);
+        }
+        self.sub_tlvs
+            .iter()
+            .enumerate()
+            .skip(usize::from(siemens))
+            .find(|(_, sub_tlv)| matches!(sub_tlv, MRPSubTlv::Ed1 { .. }))
+            .map(|(index, _)| index)
+    }
+
+    pub fn payload_length(&self) -> usize {
+        3 + self
+            .sub_tlvs
+            .iter()
+            .map(MRPSubTlv::encoded_len)
+            .sum::<usize>()
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.manufacturer_oui);
+        for sub_tlv in &self.sub_tlvs {
+            sub_tlv.write_bytes(buf);
         }
     }
+
+    /// The TLV value as lowercase hex in groups of four bytes, e.g. "08000600 0000".
+    pub fn to_hex_string(&self) -> String {
+        use core::fmt::Write;
+
+        let mut value = Vec::with_capacity(self.payload_length());
+        self.write_bytes(&mut value);
+        let mut text = String::with_capacity(value.len() * 2 + value.len() / 4);
+        for (i, byte) in value.iter().enumerate() {
+            if i > 0 && i % 4 == 0 {
+                text.push(' ');
+            }
+            let _ = write!(text, "{:02x}", byte);
+        }
+        text
+    }
+}
+
+impl MRPSubTlv {
+    /// Sub-type byte on the wire; `None` for [`MRPSubTlv::Ed1`], which has no sub-TLV header.
+    pub fn sub_type(&self) -> Option<u8> {
+        match self {
+            MRPSubTlv::Ed1 { .. } => None,
+            MRPSubTlv::TestMgrNAck(_) => Some(0x01),
+            MRPSubTlv::TestPropagate(_) => Some(0x02),
+            MRPSubTlv::AutoMgr => Some(0x03),
+            MRPSubTlv::Raw { sub_type, .. } => Some(*sub_type),
+        }
+    }
+
+    /// Bytes this entry occupies on the wire, including the sub-TLV header.
+    pub fn encoded_len(&self) -> usize {
+        match self {
+            MRPSubTlv::Ed1 { .. } => 3,
+            MRPSubTlv::TestMgrNAck(_) | MRPSubTlv::TestPropagate(_) => 2 + 16,
+            MRPSubTlv::AutoMgr => 2,
+            MRPSubTlv::Raw { value, .. } => 2 + value.len(),
+        }
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        if let MRPSubTlv::Ed1 {
+            ed1_type,
+            manufacturer_data,
+        } = self
+        {
+            buf.push(*ed1_type);
+            buf.extend_from_slice(&manufacturer_data.to_be_bytes());
+            return;
+        }
+        buf.extend(self.sub_type());
+        // A longer value makes the option too long for its own length byte, so encoding has
+        // already failed with EncodeError::ValueTooLong.
+        buf.push(u8::try_from(self.encoded_len() - 2).unwrap_or(u8::MAX));
+        match self {
+            MRPSubTlv::TestMgrNAck(data) | MRPSubTlv::TestPropagate(data) => data.write_bytes(buf),
+            MRPSubTlv::Raw { value, .. } => buf.extend_from_slice(value),
+            MRPSubTlv::Ed1 { .. } | MRPSubTlv::AutoMgr => {}
+        }
+    }
+}
+
+impl MRPManagerData {
+    pub fn new(prio: u16, sa: MacAddress, other_prio: u16, other_sa: MacAddress) -> Self {
+        MRPManagerData {
+            prio,
+            sa,
+            other_prio,
+            other_sa,
+        }
+    }
+
+    pub fn write_bytes(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.prio.to_be_bytes());
+        buf.extend_from_slice(&self.sa.0);
+        buf.extend_from_slice(&self.other_prio.to_be_bytes());
+        buf.extend_from_slice(&self.other_sa.0);
+    }
+}
+
+fn ring_state_label(state: RingState) -> &'static str {
+    match state {
+        RingState::Open => "open",
+        RingState::Closed => "closed",
+        RingState::Unknown(_) => "unknown",
+    }
+}
+
+fn port_role_label(role: PortRole) -> &'static str {
+    match role {
+        PortRole::Primary => "primary",
+        PortRole::Secondary => "secondary",
+        PortRole::Interconnection => "interconnection",
+        PortRole::Unknown(_) => "unknown",
+    }
+}
+
+impl fmt::Display for MRPData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "MRP Version: {:#06x}", self.version)?;
+        for header in &self.tlv_headers {
+            write!(f, "{}", header)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MRPTLVHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "  TLV Type: {:#04x}, Length: {}\n  Data:\n{}",
+            self.tlv_type, self.length, self.data
+        )
+    }
+}
+
+impl fmt::Display for MRPTLVData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MRPTLVData::MRPTest(data) => write!(f, "{}", data),
+            MRPTLVData::MRPCommon(data) => write!(f, "{}", data),
+            MRPTLVData::MRPTopologyChange(data) => write!(f, "{}", data),
+            MRPTLVData::MRPLinkDown(data) => write!(f, "    MRP LinkDown Data:\n{}", data),
+            MRPTLVData::MRPLinkUp(data) => write!(f, "    MRP LinkUp Data:\n{}", data),
+            MRPTLVData::MRPInTest(data) => write!(f, "{}", data),
+            MRPTLVData::MRPInTopologyChange(data) => write!(f, "{}", data),
+            MRPTLVData::MRPInLinkDown(data) => write!(f, "    MRP InLinkDown Data:\n{}", data),
+            MRPTLVData::MRPInLinkUp(data) => write!(f, "    MRP InLinkUp Data:\n{}", data),
+            MRPTLVData::MRPInLinkStatusPoll(data) => write!(f, "{}", data),
+            MRPTLVData::MRPOption(data) => write!(f, "{}", data),
+            MRPTLVData::MRPEnd => writeln!(f, "    End of MRP Data"),
+            MRPTLVData::Unknown { tlv_type, raw } => {
+                write!(f, "    Unknown TLV Data ({:#04x}):\n      Raw:", tlv_type)?;
+                for byte in raw {
+                    write!(f, " {:02x}", byte)?;
+                }
+                writeln!(f)
+            }
+        }
+    }
+}
+
+impl fmt::Display for PortRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PortRole::Primary => write!(f, "Primary"),
+            PortRole::Secondary => write!(f, "Secondary"),
+            PortRole::Interconnection => write!(f, "Interconnection"),
+            PortRole::Unknown(_) => write!(f, "Unknown"),
+        }
+    }
+}
+
+impl fmt::Display for RingState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RingState::Open => write!(f, "Open"),
+            RingState::Closed => write!(f, "Closed"),
+            RingState::Unknown(_) => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// Wrapping difference `later - earlier` of two MRP timestamps, `None` if `earlier` is ahead.
+fn timestamp_age(later: u32, earlier: u32) -> Option<Duration> {
+    let diff = later.wrapping_sub(earlier);
+    (diff < 0x8000_0000).then(|| Duration::from_millis(diff.into()))
+}
+
+/// Formats a millisecond count as e.g. `435 830 740 ms ≈ 5d 1h`.
+struct Millis(u64);
+
+impl fmt::Display for Millis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits = alloc::format!("{}", self.0);
+        for (i, digit) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}", digit)?;
+        }
+        f.write_str(" ms")?;
+
+        let seconds = self.0 / 1000;
+        if seconds == 0 {
+            return Ok(());
+        }
+        // The two most significant non-zero units are plenty for a human.
+        let units = [
+            (seconds / 86_400, "d"),
+            (seconds / 3600 % 24, "h"),
+            (seconds / 60 % 60, "m"),
+            (seconds % 60, "s"),
+        ];
+        let first = units.iter().position(|(n, _)| *n > 0).unwrap_or(3);
+        write!(f, " ≈ {}{}", units[first].0, units[first].1)?;
+        if let Some((n, unit)) = units.get(first + 1).filter(|(n, _)| *n > 0) {
+            write!(f, " {}{}", n, unit)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MRPTestData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP Test Data:\n      Prio: {:#06x}\n      SA: {}\n      Port Role: {:#06x} ({})\n      Ring State: {:#06x} ({})\n      Transition: {:#06x}\n      Timestamp: {:#010x} ({})\n",
+            self.prio,
+            self.sa,
+            u16::from(self.port_role),
+            self.port_role,
+            u16::from(self.ring_state),
+            self.ring_state,
+            self.transition,
+            self.timestamp,
+            Millis(self.timestamp.into())
+        )
+    }
+}
+
+impl fmt::Display for MRPCommonData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP Common Data:\n      Sequence ID: {:#06x}\n      Domain UUID: {}\n",
+            self.sequence_id, self.domain_uuid
+        )
+    }
+}
+
+impl fmt::Display for MRPTopologyChangeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP TopologyChange Data:\n      Prio: {:#06x}\n      SA: {}\n      Interval: {:#06x} ({})\n",
+            self.prio,
+            self.sa,
+            self.interval,
+            Millis(self.interval.into())
+        )
+    }
+}
+
+impl fmt::Display for MRPLinkData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "      SA: {}\n      Port Role: {:#06x} ({})\n      Interval: {:#06x} ({})\n      Blocked: {:#06x}\n",
+            self.sa,
+            u16::from(self.port_role),
+            self.port_role,
+            self.interval,
+            Millis(self.interval.into()),
+            self.blocked
+        )
+    }
+}
+
+impl fmt::Display for MRPInTestData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP InTest Data:\n      InID: {:#06x}\n      SA: {}\n      Port Role: {:#06x} ({})\n      InState: {:#06x} ({})\n      Transition: {:#06x}\n      Timestamp: {:#010x} ({})\n",
+            self.in_id,
+            self.sa,
+            u16::from(self.port_role),
+            self.port_role,
+            u16::from(self.in_state),
+            self.in_state,
+            self.transition,
+            self.timestamp,
+            Millis(self.timestamp.into())
+        )
+    }
+}
+
+impl fmt::Display for MRPInTopologyChangeData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP InTopologyChange Data:\n      SA: {}\n      InID: {:#06x}\n      Interval: {:#06x} ({})\n",
+            self.sa,
+            self.in_id,
+            self.interval,
+            Millis(self.interval.into())
+        )
+    }
+}
+
+impl fmt::Display for MRPInLinkData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "      SA: {}\n      Port Role: {:#06x} ({})\n      InID: {:#06x}\n      Interval: {:#06x} ({})\n      LinkInfo: {:#06x}\n",
+            self.sa,
+            u16::from(self.port_role),
+            self.port_role,
+            self.in_id,
+            self.interval,
+            Millis(self.interval.into()),
+            self.link_info
+        )
+    }
+}
+
+impl fmt::Display for MRPInLinkStatusPollData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP InLinkStatusPoll Data:\n      SA: {}\n      Port Role: {:#06x} ({})\n      InID: {:#06x}\n",
+            self.sa,
+            u16::from(self.port_role),
+            self.port_role,
+            self.in_id
+        )
+    }
+}
+
+impl fmt::Display for MRPOptionData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "    MRP Option Data:\n      Manufacturer OUI: {:02x}:{:02x}:{:02x}\n",
+            self.manufacturer_oui[0], self.manufacturer_oui[1], self.manufacturer_oui[2]
+        )?;
+        for sub_tlv in &self.sub_tlvs {
+            write!(f, "{}", sub_tlv)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for MRPSubTlv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MRPSubTlv::Ed1 {
+                ed1_type,
+                manufacturer_data,
+            } => write!(
+                f,
+                "      Ed1 Type: {:#04x}\n      Ed1 Manufacturer Data: {:#06x}\n",
+                ed1_type, manufacturer_data
+            ),
+            MRPSubTlv::TestMgrNAck(data) => write!(f, "      MRP TestMgrNAck:\n{}", data),
+            MRPSubTlv::TestPropagate(data) => write!(f, "      MRP TestPropagate:\n{}", data),
+            MRPSubTlv::AutoMgr => writeln!(f, "      MRP AutoMgr"),
+            MRPSubTlv::Raw { sub_type, value } => {
+                write!(
+                    f,
+                    "      Unknown Sub-TLV ({:#04x}):\n        Raw:",
+                    sub_type
+                )?;
+                for byte in value {
+                    write!(f, " {:02x}", byte)?;
+                }
+                writeln!(f)
+            }
+        }
+    }
+}
+
+impl fmt::Display for MRPManagerData {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "        Prio: {:#06x}\n        SA: {}\n        Other Prio: {:#06x}\n        Other SA: {}\n",
+            self.prio, self.sa, self.other_prio, self.other_sa
+        )
+    }
+}
+
+/// # Panics
+///
+/// Panics if `data` is not exactly 6 bytes long; see [`try_parse_mac_address`].
+pub fn parse_mac_address(data: &[u8]) -> MacAddress {
+    MacAddress::try_from(data).expect("MAC address must be 6 bytes")
+}
+
+/// # Panics
+///
+/// Panics if `data` is shorter than 2 bytes; see [`try_parse_u16`].
+pub fn parse_u16(data: &[u8]) -> u16 {
+    u16::from_be_bytes([data[0], data[1]])
+}
+
+/// # Panics
+///
+/// Panics if `data` is shorter than 4 bytes; see [`try_parse_u32`].
+pub fn parse_u32(data: &[u8]) -> u32 {
+    u32::from_be_bytes([data[0], data[1], data[2], data[3]])
+}
+
+fn take<const N: usize>(data: &[u8]) -> Result<[u8; N], MRPParseError> {
+    data.get(..N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(MRPParseError::InsufficientData {
+            needed: N,
+            available: data.len(),
+        })
+}
+
+/// Reads a MAC address from the first 6 bytes of `data`.
+pub fn try_parse_mac_address(data: &[u8]) -> Result<MacAddress, MRPParseError> {
+    take::<6>(data).map(MacAddress)
+}
+
+/// Reads a big-endian `u16` from the first 2 bytes of `data`.
+pub fn try_parse_u16(data: &[u8]) -> Result<u16, MRPParseError> {
+    take::<2>(data).map(u16::from_be_bytes)
+}
+
+/// Reads a big-endian `u32` from the first 4 bytes of `data`.
+pub fn try_parse_u32(data: &[u8]) -> Result<u32, MRPParseError> {
+    take::<4>(data).map(u32::from_be_bytes)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MRPParseError {
+    /// The input is too short to hold the 2-byte MRP version and whatever precedes it: nothing
+    /// for [`parse_mrp_data`], the 6-byte source MAC for
+    /// [`frame::parse_mrp_data_with_source_mac`] (`needed` is then 8).
+    InsufficientData {
+        needed: usize,
+        available: usize,
+    },
+    /// A TLV header starts at `offset` but the payload ends before its length byte.
+    TruncatedHeader {
+        offset: usize,
+    },
+    /// A TLV at `offset` declares `expected` value bytes but only `available` remain.
+    TruncatedTlvValue {
+        offset: usize,
+        tlv_type: u8,
+        expected: usize,
+        available: usize,
+    },
+    /// A TLV declares fewer value bytes than its type requires.
+    InvalidTlvLength {
+        tlv_type: u8,
+        expected: usize,
+        actual: usize,
+    },
+    /// A sub-TLV at `offset` within an MRP_Option value runs past the end of that value.
+    TruncatedSubTlv {
+        offset: usize,
+    },
+    /// A known sub-TLV declares a different number of value bytes than its type requires.
+    InvalidSubTlvLength {
+        sub_type: u8,
+        expected: usize,
+        actual: usize,
+    },
+    UnknownTlvType(u8),
+    InvalidUuid,
+    /// The payload is longer than [`MRP_MAX_PDU_SIZE`].
+    FrameTooLong {
+        actual: usize,
+        max: usize,
+    },
+    /// The version is not [`MRP_VERSION`] and the parser is strict about it.
+    UnsupportedVersion(u16),
+    /// The PDU holds more TLVs than the parser allows.
+    TooManyTlvs {
+        max: usize,
+    },
+    /// The PDU does not end with MRP_End and the parser is strict about it.
+    MissingEnd,
+    /// The environment variable passed to [`parse_mrp_data_from_env`] is not set.
+    EnvVarNotFound(String),
+    /// The input is not an even number of hex digits.
+    InvalidHex,
+    /// The JSON passed to `parse_mrp_data_from_json_value` has no string `"hex"` field.
+    MalformedJsonInput,
+    /// The buffer ends inside the PDU; see [`reader::TlvReader`].
+    NeedMoreData {
+        missing: usize,
+    },
+    /// Reading the PDU from a socket failed, e.g. in [`parse_mrp_frame_from_unix_socket`].
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+impl fmt::Display for MRPParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MRPParseError::InsufficientData { needed, available } => write!(
+                f,
+                "insufficient data: needed {} bytes, {} available",
+                needed, available
+            ),
+            MRPParseError::TruncatedHeader { offset } => {
+                write!(f, "truncated TLV header at offset {}", offset)
+            }
+            MRPParseError::TruncatedTlvValue {
+                offset,
+                tlv_type,
+                expected,
+                available,
+            } => write!(
+                f,
+                "truncated value for TLV type {:#04x} at offset {}: expected {} bytes, {} available",
+                tlv_type, offset, expected, available
+            ),
+            MRPParseError::InvalidTlvLength {
+                tlv_type,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid length for TLV type {:#04x}: expected at least {} bytes, got {}",
+                tlv_type, expected, actual
+            ),
+            MRPParseError::TruncatedSubTlv { offset } => {
+                write!(f, "truncated MRP_Option sub-TLV at offset {}", offset)
+            }
+            MRPParseError::InvalidSubTlvLength {
+                sub_type,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "invalid length for sub-TLV type {:#04x}: expected {} bytes, got {}",
+                sub_type, expected, actual
+            ),
+            MRPParseError::UnknownTlvType(tlv_type) => {
+                write!(f, "unknown TLV type {:#04x}", tlv_type)
+            }
+            MRPParseError::InvalidUuid => write!(f, "invalid domain UUID in MRP_Common TLV"),
+            MRPParseError::FrameTooLong { actual, max } => {
+                write!(f, "MRP payload is {} bytes, at most {} allowed", actual, max)
+            }
+            MRPParseError::UnsupportedVersion(version) => {
+                write!(f, "unsupported MRP version {:#06x}", version)
+            }
+            MRPParseError::TooManyTlvs { max } => {
+                write!(f, "MRP payload has more than {} TLVs", max)
+            }
+            MRPParseError::MissingEnd => write!(f, "MRP payload does not end with MRP_End"),
+            MRPParseError::EnvVarNotFound(name) => {
+                write!(f, "environment variable {} is not set", name)
+            }
+            MRPParseError::InvalidHex => write!(f, "invalid hex-encoded MRP payload"),
+            MRPParseError::MalformedJsonInput => {
+                write!(f, "JSON input has no string \"hex\" field")
+            }
+            MRPParseError::NeedMoreData { missing } => {
+                write!(f, "MRP payload is incomplete: {} more bytes needed", missing)
+            }
+            #[cfg(feature = "std")]
+            MRPParseError::Io(kind) => write!(f, "reading MRP payload failed: {}", kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MRPParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The encoded PDU is `needed` bytes but the buffer holds only `available`.
+    BufferTooSmall { needed: usize, available: usize },
+    /// A TLV value is `length` bytes, more than its one-byte length field can describe.
+    ValueTooLong { tlv_type: u8, length: usize },
+    /// MRP_Option sub-TLV `index` breaks the Ed1 rule; see [`MRPOptionData::misplaced_ed1`].
+    MisplacedEd1 { index: usize },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::BufferTooSmall { needed, available } => write!(
+                f,
+                "encoded MRP payload is {} bytes, buffer holds {}",
+                needed, available
+            ),
+            EncodeError::ValueTooLong { tlv_type, length } => write!(
+                f,
+                "TLV type {:#04x} value is {} bytes, at most 255 can be encoded",
+                tlv_type, length
+            ),
+            EncodeError::MisplacedEd1 { index } => write!(
+                f,
+                "MRP_Option sub-TLV {} breaks the rule that Ed1 leads a Siemens option only",
+                index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+fn check_tlv_length(tlv_type: u8, expected: usize, actual: usize) -> Result<(), MRPParseError> {
+    if actual < expected {
+        return Err(MRPParseError::InvalidTlvLength {
+            tlv_type,
+            expected,
+            actual,
+        });
+    }
+    Ok(())
+}
+
+fn parse_test_data(tlv_data: &[u8]) -> Result<MRPTestData, MRPParseError> {
+    check_tlv_length(0x02, 18, tlv_data.len())?;
+    Ok(MRPTestData {
+        prio: try_parse_u16(tlv_data)?,
+        sa: try_parse_mac_address(&tlv_data[2..])?,
+        port_role: try_parse_u16(&tlv_data[8..])?.into(),
+        ring_state: try_parse_u16(&tlv_data[10..])?.into(),
+        transition: try_parse_u16(&tlv_data[12..])?,
+        timestamp: try_parse_u32(&tlv_data[14..])?,
+    })
+}
+
+fn parse_common_data(tlv_data: &[u8]) -> Result<MRPCommonData, MRPParseError> {
+    check_tlv_length(0x01, 18, tlv_data.len())?;
+    Ok(MRPCommonData {
+        sequence_id: try_parse_u16(tlv_data)?,
+        domain_uuid: tlv_data
+            .get(2..18)
+            .and_then(|bytes| Uuid::from_slice(bytes).ok())
+            .ok_or(MRPParseError::InvalidUuid)?,
+    })
+}
+
+/// Reads exactly `N` value bytes; a reader that ends early reports `UnexpectedEof`.
+#[cfg(feature = "std")]
+fn read_tlv_value<R: std::io::Read, const N: usize>(r: &mut R) -> Result<[u8; N], MRPParseError> {
+    let mut value = [0u8; N];
+    r.read_exact(&mut value)
+        .map_err(|err| MRPParseError::Io(err.kind()))?;
+    Ok(value)
+}
+
+fn parse_option_data(tlv_data: &[u8]) -> Result<MRPOptionData, MRPParseError> {
+    let manufacturer_oui = take::<3>(tlv_data)?;
+    let mut sub_tlvs = Vec::new();
+    let mut offset = 3;
+    if manufacturer_oui == SIEMENS_OUI {
+        check_tlv_length(0x7f, 6, tlv_data.len())?;
+        sub_tlvs.push(MRPSubTlv::Ed1 {
+            ed1_type: tlv_data[3],
+            manufacturer_data: try_parse_u16(&tlv_data[4..])?,
+        });
+        offset = 6;
+    }
+
+    while offset < tlv_data.len() {
+        let (sub_type, length) = match tlv_data.get(offset..offset + 2) {
+            Some(header) => (header[0], header[1] as usize),
+            None => return Err(MRPParseError::TruncatedSubTlv { offset }),
+        };
+        let value = tlv_data
+            .get(offset + 2..offset + 2 + length)
+            .ok_or(MRPParseError::TruncatedSubTlv { offset })?;
+        let check_length = |expected: usize| {
+            if length == expected {
+                Ok(())
+            } else {
+                Err(MRPParseError::InvalidSubTlvLength {
+                    sub_type,
+                    expected,
+                    actual: length,
+                })
+            }
+        };
+        let manager_data = || -> Result<MRPManagerData, MRPParseError> {
+            check_length(16)?;
+            Ok(MRPManagerData {
+                prio: try_parse_u16(value)?,
+                sa: try_parse_mac_address(&value[2..])?,
+                other_prio: try_parse_u16(&value[8..])?,
+                other_sa: try_parse_mac_address(&value[10..])?,
+            })
+        };
+        sub_tlvs.push(match sub_type {
+            0x01 => MRPSubTlv::TestMgrNAck(manager_data()?),
+            0x02 => MRPSubTlv::TestPropagate(manager_data()?),
+            0x03 => {
+                check_length(0)?;
+                MRPSubTlv::AutoMgr
+            }
+            _ => MRPSubTlv::Raw {
+                sub_type,
+                value: value.to_vec(),
+            },
+        });
+        offset += 2 + length;
+    }
+
+    Ok(MRPOptionData {
+        manufacturer_oui,
+        sub_tlvs,
+    })
+}
+
+/// The MRP version defined by IEC 62439-2.
+pub const MRP_VERSION: u16 = 0x0001;
+
+/// Largest MRP payload accepted by the parser.
+pub const MRP_MAX_PDU_SIZE: usize = 1498;
+
+/// What the parser does with a TLV type it does not know.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UnknownTlvPolicy {
+    /// Keep the TLV as [`MRPTLVData::Unknown`] and continue with the next one.
+    #[default]
+    Keep,
+    /// Fail with [`MRPParseError::UnknownTlvType`].
+    Reject,
+}
+
+/// Decodes the value bytes of a single TLV of type `tlv_type`.
+pub fn parse_tlv_data(
+    tlv_type: u8,
+    tlv_data: &[u8],
+    policy: UnknownTlvPolicy,
+) -> Result<MRPTLVData, MRPParseError> {
+    Ok(match tlv_type {
+        0x02 => {
+            //print(!("Parsing MRPTest TLV");
+            MRPTLVData::MRPTest(parse_test_data(tlv_data)?)
+        }
+        0x01 => {
+            //print(!("Parsing MRPCommon TLV");
+            MRPTLVData::MRPCommon(parse_common_data(tlv_data)?)
+        }
+        0x03 => {
+            check_tlv_length(tlv_type, 10, tlv_data.len())?;
+            MRPTLVData::MRPTopologyChange(MRPTopologyChangeData {
+                prio: try_parse_u16(tlv_data)?,
+                sa: try_parse_mac_address(&tlv_data[2..])?,
+                interval: try_parse_u16(&tlv_data[8..])?,
+            })
+        }
+        0x04 | 0x05 => {
+            check_tlv_length(tlv_type, 12, tlv_data.len())?;
+            let link = MRPLinkData {
+                sa: try_parse_mac_address(tlv_data)?,
+                port_role: try_parse_u16(&tlv_data[6..])?.into(),
+                interval: try_parse_u16(&tlv_data[8..])?,
+                blocked: try_parse_u16(&tlv_data[10..])?,
+            };
+            if tlv_type == 0x04 {
+                MRPTLVData::MRPLinkDown(link)
+            } else {
+                MRPTLVData::MRPLinkUp(link)
+            }
+        }
+        0x06 => {
+            check_tlv_length(tlv_type, 18, tlv_data.len())?;
+            MRPTLVData::MRPInTest(MRPInTestData {
+                in_id: try_parse_u16(tlv_data)?,
+                sa: try_parse_mac_address(&tlv_data[2..])?,
+                port_role: try_parse_u16(&tlv_data[8..])?.into(),
+                in_state: try_parse_u16(&tlv_data[10..])?.into(),
+                transition: try_parse_u16(&tlv_data[12..])?,
+                timestamp: try_parse_u32(&tlv_data[14..])?,
+            })
+        }
+        0x07 => {
+            check_tlv_length(tlv_type, 10, tlv_data.len())?;
+            MRPTLVData::MRPInTopologyChange(MRPInTopologyChangeData {
+                sa: try_parse_mac_address(tlv_data)?,
+                in_id: try_parse_u16(&tlv_data[6..])?,
+                interval: try_parse_u16(&tlv_data[8..])?,
+            })
+        }
+        0x08 | 0x09 => {
+            check_tlv_length(tlv_type, 14, tlv_data.len())?;
+            let link = MRPInLinkData {
+                sa: try_parse_mac_address(tlv_data)?,
+                port_role: try_parse_u16(&tlv_data[6..])?.into(),
+                in_id: try_parse_u16(&tlv_data[8..])?,
+                interval: try_parse_u16(&tlv_data[10..])?,
+                link_info: try_parse_u16(&tlv_data[12..])?,
+            };
+            if tlv_type == 0x08 {
+                MRPTLVData::MRPInLinkDown(link)
+            } else {
+                MRPTLVData::MRPInLinkUp(link)
+            }
+        }
+        0x0a => {
+            check_tlv_length(tlv_type, 10, tlv_data.len())?;
+            MRPTLVData::MRPInLinkStatusPoll(MRPInLinkStatusPollData {
+                sa: try_parse_mac_address(tlv_data)?,
+                port_role: try_parse_u16(&tlv_data[6..])?.into(),
+                in_id: try_parse_u16(&tlv_data[8..])?,
+            })
+        }
+        0x7f => {
+            //print(!("Parsing MRPOption TLV");
+            check_tlv_length(tlv_type, 3, tlv_data.len())?;
+            MRPTLVData::MRPOption(parse_option_data(tlv_data)?)
+        }
+        0x00 => {
+            //print(!("Parsing MRPEnd TLV");
+            MRPTLVData::MRPEnd
+        }
+        _ => {
+            //print(!("Unknown TLV type");
+            if policy == UnknownTlvPolicy::Reject {
+                return Err(MRPParseError::UnknownTlvType(tlv_type));
+            }
+            MRPTLVData::Unknown {
+                tlv_type,
+                raw: tlv_data.to_vec(),
+            }
+        }
+    })
+}
+
+pub fn parse_mrp_data(data: &[u8]) -> Result<MRPData, MRPParseError> {
+    parse_mrp_data_with_policy(data, UnknownTlvPolicy::Keep)
+}
+
+pub fn parse_mrp_data_with_policy(
+    data: &[u8],
+    policy: UnknownTlvPolicy,
+) -> Result<MRPData, MRPParseError> {
+    if data.len() > MRP_MAX_PDU_SIZE {
+        return Err(MRPParseError::FrameTooLong {
+            actual: data.len(),
+            max: MRP_MAX_PDU_SIZE,
+        });
+    }
+    if data.len() < 2 {
+        //print(!("Insufficient data for version");
+        return Err(MRPParseError::InsufficientData {
+            needed: 2,
+            available: data.len(),
+        });
+    }
+
+    let version = try_parse_u16(data)?;
+    //print(!("Parsed version: {:#06x}", version);
+    let tlv_headers = parse_tlvs(data, 2, policy)?;
+
+    //print(!("Parsed MRPData with {} TLV headers", tlv_headers.len());
+
+    Ok(MRPData {
+        version,
+        tlv_headers,
+    })
+}
+
+/// An anomaly [`parse_mrp_data_with_warnings`] tolerated while parsing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarning {
+    /// A TLV of this unknown type was left out of the result.
+    UnknownTlvSkipped(u8),
+    /// The TLV's length byte does not match the size of its type's value; extra bytes were ignored.
+    LengthFieldMismatch { tlv_type: u8 },
+    /// Bytes after MRP_End, usually Ethernet padding, were ignored.
+    PaddingBytesIgnored,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarning::UnknownTlvSkipped(tlv_type) => {
+                write!(f, "skipped unknown TLV type {:#04x}", tlv_type)
+            }
+            ParseWarning::LengthFieldMismatch { tlv_type } => {
+                write!(
+                    f,
+                    "length field of TLV type {:#04x} does not match its value",
+                    tlv_type
+                )
+            }
+            ParseWarning::PaddingBytesIgnored => write!(f, "ignored bytes after MRP_End"),
+        }
+    }
+}
+
+/// Like [`parse_mrp_data`], but drops unknown TLVs and reports anomalies alongside the result.
+///
+/// Warnings collected before a parse error are still returned.
+pub fn parse_mrp_data_with_warnings(
+    data: &[u8],
+) -> (Result<MRPData, MRPParseError>, Vec<ParseWarning>) {
+    let mut warnings = Vec::new();
+    let result = borrowed::parse_mrp_data_ref(data).and_then(|mrp_ref| {
+        let mut tlv_headers = Vec::new();
+        let mut end = 2;
+        for tlv in mrp_ref.iter() {
+            let tlv = tlv?;
+            end += 2 + tlv.value.len();
+            let header = tlv.to_owned()?;
+            if let MRPTLVData::Unknown { tlv_type, .. } = header.data {
+                warnings.push(ParseWarning::UnknownTlvSkipped(tlv_type));
+                continue;
+            }
+            if tlv.value.len() != header.data.payload_length() {
+                warnings.push(ParseWarning::LengthFieldMismatch {
+                    tlv_type: tlv.tlv_type,
+                });
+            }
+            tlv_headers.push(header);
+        }
+        if end < data.len() {
+            warnings.push(ParseWarning::PaddingBytesIgnored);
+        }
+        Ok(MRPData {
+            version: mrp_ref.version,
+            tlv_headers,
+        })
+    });
+    (result, warnings)
+}
+
+/// Parses a PDU followed by other data, returning the bytes after MRP_End.
+///
+/// Only the PDU itself is held to [`MRP_MAX_PDU_SIZE`]. Without an MRP_End the whole buffer is
+/// taken as TLVs and the remainder is empty.
+pub fn parse_mrp_data_with_remainder(data: &[u8]) -> Result<(MRPData, &[u8]), MRPParseError> {
+    let version = try_parse_u16(data)?;
+    let mut tlv_headers = Vec::new();
+    let mut end = 2;
+    for tlv in borrowed::MrpTlvIter::new(data, 2) {
+        let tlv = tlv?;
+        end += 2 + tlv.value.len();
+        tlv_headers.push(tlv.to_owned()?);
+    }
+    if end > MRP_MAX_PDU_SIZE {
+        return Err(MRPParseError::FrameTooLong {
+            actual: end,
+            max: MRP_MAX_PDU_SIZE,
+        });
+    }
+    Ok((
+        MRPData {
+            version,
+            tlv_headers,
+        },
+        &data[end..],
+    ))
+}
+
+/// Like [`parse_mrp_data`], also returning `(tlv_type, bytes)` for each TLV in order.
+///
+/// `bytes` includes the 2-byte TLV header. The version field and any padding after MRP_End
+/// are not attributed to a TLV, so for an unpadded PDU the counts sum to `data.len() - 2`.
+pub fn parse_mrp_data_with_byte_accounting(
+    data: &[u8],
+) -> Result<(MRPData, Vec<(u8, usize)>), MRPParseError> {
+    let mrp_ref = borrowed::parse_mrp_data_ref(data)?;
+    let mut tlv_headers = Vec::new();
+    let mut accounting = Vec::new();
+    for tlv in mrp_ref.iter() {
+        let tlv = tlv?;
+        accounting.push((tlv.tlv_type, 2 + tlv.value.len()));
+        tlv_headers.push(tlv.to_owned()?);
+    }
+    Ok((
+        MRPData {
+            version: mrp_ref.version,
+            tlv_headers,
+        },
+        accounting,
+    ))
+}
+
+/// Parses a TLV sequence that has no leading version, such as one embedded in another
+/// protocol. Error offsets are relative to `data`.
+pub fn parse_mrp_tlvs_only(data: &[u8]) -> Result<Vec<MRPTLVHeader>, MRPParseError> {
+    parse_tlvs(data, 0, UnknownTlvPolicy::Keep)
+}
+
+fn parse_tlvs(
+    data: &[u8],
+    offset: usize,
+    policy: UnknownTlvPolicy,
+) -> Result<Vec<MRPTLVHeader>, MRPParseError> {
+    borrowed::collect_tlvs(borrowed::MrpTlvIter::new(data, offset), policy)
+}
+
+/// Diagnostic sink set by [`MRPParser::with_custom_logger`].
+type Logger = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// A configurable parser; [`MRPParser::default`] behaves like [`parse_mrp_data`].
+#[derive(Clone)]
+pub struct MRPParser {
+    policy: UnknownTlvPolicy,
+    max_tlvs: usize,
+    strict_version: bool,
+    strict_end: bool,
+    logger: Option<Logger>,
+}
+
+impl fmt::Debug for MRPParser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MRPParser")
+            .field("policy", &self.policy)
+            .field("max_tlvs", &self.max_tlvs)
+            .field("strict_version", &self.strict_version)
+            .field("strict_end", &self.strict_end)
+            .field("logger", &self.logger.as_ref().map(|_| ".."))
+            .finish()
+    }
+}
+
+/// Parsers are equal when configured alike and sharing the same logger, if any.
+impl PartialEq for MRPParser {
+    fn eq(&self, other: &Self) -> bool {
+        self.policy == other.policy
+            && self.max_tlvs == other.max_tlvs
+            && self.strict_version == other.strict_version
+            && self.strict_end == other.strict_end
+            && match (&self.logger, &other.logger) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+    }
+}
+
+impl Default for MRPParser {
+    fn default() -> Self {
+        MRPParser {
+            policy: UnknownTlvPolicy::Keep,
+            max_tlvs: usize::MAX,
+            strict_version: false,
+            strict_end: false,
+            logger: None,
+        }
+    }
+}
+
+impl MRPParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn policy(mut self, policy: UnknownTlvPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Fails with [`MRPParseError::TooManyTlvs`] once more than `max_tlvs` TLVs are seen.
+    pub fn max_tlvs(mut self, max_tlvs: usize) -> Self {
+        self.max_tlvs = max_tlvs;
+        self
+    }
+
+    /// Fails with [`MRPParseError::UnsupportedVersion`] unless the version is [`MRP_VERSION`].
+    pub fn strict_version(mut self, strict_version: bool) -> Self {
+        self.strict_version = strict_version;
+        self
+    }
+
+    /// Fails with [`MRPParseError::MissingEnd`] unless the last TLV is MRP_End.
+    pub fn strict_end(mut self, strict_end: bool) -> Self {
+        self.strict_end = strict_end;
+        self
+    }
+
+    /// Calls `logger` with a line for the PDU, each TLV and the outcome of every
+    /// [`parse`](MRPParser::parse). Messages are only formatted when a logger is set.
+    pub fn with_custom_logger(mut self, logger: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.logger = Some(Arc::new(logger));
+        self
+    }
+
+    fn log(&self, message: fmt::Arguments<'_>) {
+        if let Some(logger) = &self.logger {
+            logger(&alloc::format!("{}", message));
+        }
+    }
+
+    pub fn parse(&self, data: &[u8]) -> Result<MRPData, MRPParseError> {
+        let result = self.parse_logged(data);
+        match &result {
+            Ok(mrp_data) => self.log(format_args!("parsed {} TLVs", mrp_data.tlv_headers.len())),
+            Err(err) => self.log(format_args!("parse failed: {}", err)),
+        }
+        result
+    }
+
+    fn parse_logged(&self, data: &[u8]) -> Result<MRPData, MRPParseError> {
+        let mrp_ref = borrowed::parse_mrp_data_ref(data)?;
+        self.log(format_args!(
+            "{}-byte PDU, version {:#06x}",
+            data.len(),
+            mrp_ref.version
+        ));
+        if self.strict_version && mrp_ref.version != MRP_VERSION {
+            return Err(MRPParseError::UnsupportedVersion(mrp_ref.version));
+        }
+
+        let mut tlv_headers = Vec::new();
+        let mut offset = 2;
+        for tlv in mrp_ref.iter() {
+            if tlv_headers.len() == self.max_tlvs {
+                return Err(MRPParseError::TooManyTlvs { max: self.max_tlvs });
+            }
+            let tlv = tlv?;
+            self.log(format_args!(
+                "TLV type {:#04x}, length {} at offset {}",
+                tlv.tlv_type,
+                tlv.value.len(),
+                offset
+            ));
+            offset += 2 + tlv.value.len();
+            tlv_headers.push(tlv.to_owned_with_policy(self.policy)?);
+        }
+        if self.strict_end
+            && !matches!(
+                tlv_headers.last(),
+                Some(MRPTLVHeader {
+                    data: MRPTLVData::MRPEnd,
+                    ..
+                })
+            )
+        {
+            return Err(MRPParseError::MissingEnd);
+        }
+
+        Ok(MRPData {
+            version: mrp_ref.version,
+            tlv_headers,
+        })
+    }
+}
+
+/// Decodes hex digits, ignoring whitespace between them.
+fn decode_hex(text: &str) -> Result<Vec<u8>, MRPParseError> {
+    let digits: Vec<u8> = text
+        .bytes()
+        .filter(|byte| !byte.is_ascii_whitespace())
+        .map(|byte| match byte {
+            b'0'..=b'9' => Ok(byte - b'0'),
+            b'a'..=b'f' => Ok(byte - b'a' + 10),
+            b'A'..=b'F' => Ok(byte - b'A' + 10),
+            _ => Err(MRPParseError::InvalidHex),
+        })
+        .collect::<Result<_, _>>()?;
+    if !digits.len().is_multiple_of(2) {
+        return Err(MRPParseError::InvalidHex);
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect())
+}
+
+/// Parses an MRP payload written as hex digits, such as a dump copied from Wireshark.
+pub fn parse_mrp_data_from_hex(text: &str) -> Result<MRPData, MRPParseError> {
+    parse_mrp_data(&decode_hex(text)?)
+}
+
+/// Parses the hex-encoded MRP payload held in the environment variable `var_name`.
+#[cfg(feature = "std")]
+pub fn parse_mrp_data_from_env(var_name: &str) -> Result<MRPData, MRPParseError> {
+    let text = match std::env::var(var_name) {
+        Ok(text) => text,
+        Err(std::env::VarError::NotPresent) => {
+            return Err(MRPParseError::EnvVarNotFound(var_name.into()))
+        }
+        Err(std::env::VarError::NotUnicode(_)) => return Err(MRPParseError::InvalidHex),
+    };
+    parse_mrp_data_from_hex(&text)
+}
+
+/// Binds a datagram socket at `path`, waits for one datagram and parses it as an MRP payload.
+///
+/// The socket file is removed again before returning. A datagram longer than
+/// [`MRP_MAX_PDU_SIZE`] is rejected with [`MRPParseError::FrameTooLong`].
+#[cfg(all(unix, feature = "std"))]
+pub fn parse_mrp_frame_from_unix_socket(path: &std::path::Path) -> Result<MRPData, MRPParseError> {
+    let socket = std::os::unix::net::UnixDatagram::bind(path)
+        .map_err(|err| MRPParseError::Io(err.kind()))?;
+    // One spare byte so an oversized datagram is not silently truncated to a valid length.
+    let mut buf = [0u8; MRP_MAX_PDU_SIZE + 1];
+    let received = socket.recv(&mut buf);
+    let _ = std::fs::remove_file(path);
+    let len = received.map_err(|err| MRPParseError::Io(err.kind()))?;
+    parse_mrp_data(&buf[..len])
+}
+
+/// Fills `buf`, returning `false` on a clean end of input before the first byte.
+#[cfg(any(all(unix, feature = "std"), feature = "pcap"))]
+pub(crate) fn read_or_eof<R: std::io::Read>(
+    reader: &mut R,
+    buf: &mut [u8],
+) -> std::io::Result<bool> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == std::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(true)
+}
+
+/// Reads length-prefixed MRP payloads from the named pipe at `path`, one item per PDU.
+///
+/// Each PDU is preceded by its length as a 2-byte big-endian integer. The pipe is opened on
+/// the first call to `next`, which blocks until a writer opens it; iteration ends when the
+/// writer closes the pipe between PDUs. A PDU that fails to parse is yielded as an error and
+/// reading continues with the next one; I/O errors, including a pipe that closes mid-PDU,
+/// are yielded once and end the iteration.
+#[cfg(all(unix, feature = "std"))]
+pub fn parse_mrp_frames_from_fifo(
+    path: &std::path::Path,
+) -> impl Iterator<Item = Result<MRPData, MRPParseError>> {
+    use std::io::Read;
+
+    let path = path.to_path_buf();
+    let mut fifo: Option<std::io::BufReader<std::fs::File>> = None;
+    let mut done = false;
+    let mut next_pdu = move || -> std::io::Result<Option<Vec<u8>>> {
+        if fifo.is_none() {
+            fifo = Some(std::io::BufReader::new(std::fs::File::open(&path)?));
+        }
+        let fifo = fifo.as_mut().expect("pipe was opened above");
+        let mut length = [0u8; 2];
+        if !read_or_eof(fifo, &mut length)? {
+            return Ok(None);
+        }
+        let mut pdu = vec![0u8; u16::from_be_bytes(length).into()];
+        fifo.read_exact(&mut pdu)?;
+        Ok(Some(pdu))
+    };
+    core::iter::from_fn(move || {
+        if done {
+            return None;
+        }
+        match next_pdu() {
+            Ok(Some(pdu)) => Some(parse_mrp_data(&pdu)),
+            Ok(None) => {
+                done = true;
+                None
+            }
+            Err(err) => {
+                done = true;
+                Some(Err(MRPParseError::Io(err.kind())))
+            }
+        }
+    })
+}
+
+/// Parses a capture stored as JSON in the form `{"hex": "00010212..."}`.
+#[cfg(feature = "json")]
+pub fn parse_mrp_data_from_json_value(value: &serde_json::Value) -> Result<MRPData, MRPParseError> {
+    let text = value
+        .get("hex")
+        .and_then(serde_json::Value::as_str)
+        .ok_or(MRPParseError::MalformedJsonInput)?;
+    parse_mrp_data_from_hex(text)
+}
+
+/// `Option`-returning wrapper kept for callers written against the 0.1 API.
+#[deprecated(note = "use `parse_mrp_data`, which reports why parsing failed")]
+pub fn parse_mrp_data_opt(data: &[u8]) -> Option<MRPData> {
+    parse_mrp_data(data).ok()
+}
+
+/// Parses a capture buffer shared between threads, borrowing it rather than copying it.
+pub fn parse_mrp_data_arc(data: Arc<Vec<u8>>) -> Result<MRPData, MRPParseError> {
+    parse_mrp_data(&data)
+}
+
+/// Parses a payload that is known to be valid, such as a test vector.
+///
+/// # Panics
+///
+/// If `data` does not parse; the panic names the caller's location and the parse error.
+#[track_caller]
+pub fn parse_mrp_data_unwrap(data: &[u8]) -> MRPData {
+    match parse_mrp_data(data) {
+        Ok(mrp_data) => mrp_data,
+        Err(err) => panic!("failed to parse {}-byte MRP payload: {}", data.len(), err),
+    }
+}
+
+/// MRP_Test PDU shared by the unit tests: Test, Common, a Siemens Option and End, with TLVs
+/// ending at offsets 22, 42, 50 and 52.
+#[cfg(test)]
+pub(crate) const TEST_PAYLOAD: [u8; 52] = *include_bytes!("../tests/fixtures/mrp_test.bin");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac_address() {
+        let mac_bytes = vec![0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22];
+        let mac = parse_mac_address(&mac_bytes);
+        assert_eq!(mac, MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]));
+    }
+
+    #[test]
+    fn test_mac_address_integer_round_trip() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(mac.to_u64(), 0x000e_8ce0_2f22);
+        assert_eq!(MacAddress::from_integer(mac.to_u64()), Some(mac));
+        assert_eq!(
+            MacAddress::from_integer(0xffff_ffff_ffff),
+            Some(MacAddress([0xff; 6]))
+        );
+    }
+
+    #[test]
+    fn test_mac_address_from_str() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!("00:0e:8c:e0:2f:22".parse(), Ok(mac));
+        assert_eq!("00-0E-8C-E0-2F-22".parse(), Ok(mac));
+        for bad in [
+            "",
+            "00:0e:8c:e0:2f",
+            "00:0e:8c:e0:2f:22:33",
+            "00:0e:8c:e0:2f:2",
+            "00:0e:8c:e0:2f:zz",
+            "00:0e:8c:e0:2f:+2",
+            "00-0e-8c:e0:2f:22",
+            "000e8ce02f22",
+        ] {
+            assert_eq!(
+                bad.parse::<MacAddress>(),
+                Err(MacAddressParseError),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_mac_address_try_from_str() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        for text in [
+            "00:0e:8c:e0:2f:22",
+            "00:0E:8C:E0:2F:22",
+            "00-0E-8C-E0-2F-22",
+            "00-0e-8c-e0-2f-22",
+            "000e.8ce0.2f22",
+            "000E.8CE0.2F22",
+        ] {
+            assert_eq!(MacAddress::try_from_str(text), Ok(mac), "{:?}", text);
+        }
+        for bad in [
+            "",
+            "00:0e:8c:e0:2f",
+            "00:0e:8c:e0:2f:22:33",
+            "00:0e:8c:e0:2f:zz",
+            "00-0e-8c:e0:2f:22",
+            "000e.8ce0",
+            "000e.8ce0.2f22.0000",
+            "00.0e.8c.e0.2f.22",
+            "000e8ce02f22",
+        ] {
+            assert_eq!(
+                MacAddress::try_from_str(bad),
+                Err(MacAddressParseError),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_mac_address_cisco_notation() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(mac.to_cisco_notation(), "000e.8ce0.2f22");
+        assert_eq!(MacAddress::from_cisco_notation("000e.8ce0.2f22"), Ok(mac));
+        assert_eq!(MacAddress::from_cisco_notation("000E.8CE0.2F22"), Ok(mac));
+        for mac in [transmit::MC_TEST, MacAddress([0xff; 6]), MacAddress([0; 6])] {
+            assert_eq!(
+                MacAddress::from_cisco_notation(&mac.to_cisco_notation()),
+                Ok(mac)
+            );
+        }
+        for bad in [
+            "",
+            "000e.8ce0",
+            "000e.8ce0.2f22.0000",
+            "000e.8ce0.2f2",
+            "000e.8ce0.2fzz",
+            "000e.8ce0.+f22",
+            "00:0e:8c:e0:2f:22",
+        ] {
+            assert_eq!(
+                MacAddress::from_cisco_notation(bad),
+                Err(MacAddressParseError),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_mac_address_windows_notation() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(mac.to_windows_notation(), "00-0E-8C-E0-2F-22");
+        assert_eq!(
+            MacAddress::from_windows_notation("00-0E-8C-E0-2F-22"),
+            Ok(mac)
+        );
+        assert_eq!(
+            MacAddress::from_windows_notation("00-0e-8c-e0-2f-22"),
+            Ok(mac)
+        );
+        for mac in [transmit::MC_TEST, MacAddress([0xff; 6]), MacAddress([0; 6])] {
+            assert_eq!(
+                MacAddress::from_windows_notation(&mac.to_windows_notation()),
+                Ok(mac)
+            );
+        }
+        for bad in [
+            "",
+            "00-0E-8C-E0-2F",
+            "00:0E:8C:E0:2F:22",
+            "00-0E-8C-E0-2F-2G",
+        ] {
+            assert_eq!(
+                MacAddress::from_windows_notation(bad),
+                Err(MacAddressParseError),
+                "{:?}",
+                bad
+            );
+        }
+    }
+
+    #[test]
+    fn test_mac_address_classification() {
+        for group in [
+            transmit::MC_TEST,
+            transmit::MC_CONTROL,
+            transmit::MC_INTEST,
+            transmit::MC_INCONTROL,
+        ] {
+            assert!(group.is_multicast());
+            assert!(group.is_mrp_multicast());
+            assert_eq!(group.oui(), [0x01, 0x15, 0x4e]);
+        }
+        let unicast = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert!(!unicast.is_multicast());
+        assert!(!unicast.is_mrp_multicast());
+        assert_eq!(unicast.oui(), [0x00, 0x0e, 0x8c]);
+        assert_eq!(unicast.octets(), [0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let broadcast = MacAddress([0xff; 6]);
+        assert!(broadcast.is_multicast());
+        assert!(!broadcast.is_mrp_multicast());
+        assert!(!MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x05]).is_mrp_multicast());
+    }
+
+    #[test]
+    fn test_is_reserved_for_protocol() {
+        assert!(transmit::MC_TEST.is_reserved_for_protocol(KnownProtocol::Mrp));
+        assert!(transmit::MC_INCONTROL.is_reserved_for_protocol(KnownProtocol::Mrp));
+        assert!(!MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x05])
+            .is_reserved_for_protocol(KnownProtocol::Mrp));
+        assert!(!transmit::MC_TEST.is_reserved_for_protocol(KnownProtocol::SpanningTree));
+
+        let stp = MacAddress([0x01, 0x80, 0xc2, 0x00, 0x00, 0x00]);
+        assert!(stp.is_reserved_for_protocol(KnownProtocol::SpanningTree));
+        assert!(stp.is_reserved_for_protocol(KnownProtocol::Rstp));
+        assert!(!stp.is_reserved_for_protocol(KnownProtocol::Mrp));
+        let lldp = MacAddress([0x01, 0x80, 0xc2, 0x00, 0x00, 0x0e]);
+        assert!(lldp.is_reserved_for_protocol(KnownProtocol::Lldp));
+        assert!(!lldp.is_reserved_for_protocol(KnownProtocol::SpanningTree));
+    }
+
+    #[test]
+    fn test_mac_address_apply_mask() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(mac.apply_mask(&MacAddress([0xff; 6])), mac);
+        assert_eq!(
+            mac.apply_mask(&MacAddress([0x00; 6])),
+            MacAddress([0x00; 6])
+        );
+        assert_eq!(
+            mac.apply_mask(&MacAddress([0xff, 0xff, 0xff, 0xf0, 0x0f, 0x00])),
+            MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x0f, 0x00])
+        );
+    }
+
+    #[test]
+    fn test_mac_address_format_with_separator() {
+        let mac = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(mac.format_with_separator('-'), "00-0e-8c-e0-2f-22");
+        assert_eq!(mac.format_with_separator('.'), "00.0e.8c.e0.2f.22");
+        assert_eq!(mac.format_with_separator(' '), "00 0e 8c e0 2f 22");
+        assert_eq!(mac.format_with_separator(':'), mac.to_string());
+        assert_eq!(mac.format_plain(), "000e8ce02f22");
+        assert_eq!(MacAddress([0xff; 6]).format_plain(), "ffffffffffff");
+    }
+
+    #[test]
+    fn test_mac_address_from_bytes_be_le() {
+        let bytes = [0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22];
+        assert_eq!(MacAddress::from_bytes_be(bytes), MacAddress::from(bytes));
+        assert_eq!(
+            MacAddress::from_bytes_le(bytes),
+            MacAddress([0x22, 0x2f, 0xe0, 0x8c, 0x0e, 0x00])
+        );
+        assert_eq!(
+            MacAddress::from_bytes_le(MacAddress::from_bytes_le(bytes).octets()),
+            MacAddress::from_bytes_be(bytes)
+        );
+    }
+
+    #[test]
+    fn test_mac_address_hash_key() {
+        let mut members = std::collections::HashMap::new();
+        members.insert(MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]), 1);
+        members.insert(MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]), 2);
+        assert_eq!(members.len(), 1);
+    }
+
+    #[test]
+    fn test_mac_address_from_integer_overflow() {
+        assert_eq!(MacAddress::from_integer(1 << 48), None);
+        assert_eq!(MacAddress::from_integer(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_parse_u16() {
+        let bytes = vec![0x12, 0x34];
+        let value = parse_u16(&bytes);
+        assert_eq!(value, 0x1234);
+    }
+
+    #[test]
+    fn test_parse_u32() {
+        let bytes = vec![0x12, 0x34, 0x56, 0x78];
+        let value = parse_u32(&bytes);
+        assert_eq!(value, 0x12345678);
+    }
+
+    #[test]
+    fn test_parse_mrp_data() {
+        let payload: Vec<u8> = vec![
+            0x00, 0x01, 0x02, 0x12, 0xa0, 0x00, 0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x01, 0x19, 0xfa, 0x3f, 0xd4, 0x01, 0x12, 0x05, 0x7e, 0xc3, 0xd6,
+            0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb, 0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6,
+            0x7f, 0x06, 0x08, 0x00, 0x06, 0x00, 0x00, 0x00, 0x00, 0x00,
+        ];
+
+        let mrp_data = parse_mrp_data(&payload).expect("Failed to parse MRP data");
+        //print(!("{}", mrp_data);
+
+        // Assertions pour vérifier que les données sont correctes
+        assert_eq!(mrp_data.version, 0x0001);
+        assert_eq!(mrp_data.tlv_headers.len(), 4);
+
+        if let MRPTLVData::MRPTest(data) = &mrp_data.tlv_headers[0].data {
+            assert_eq!(data.prio, 0xa000);
+            assert_eq!(data.sa.to_string(), "00:0e:8c:e0:2f:22");
+            assert_eq!(data.port_role, PortRole::Primary);
+            assert_eq!(data.ring_state, RingState::Open);
+            assert_eq!(data.transition, 0x0001);
+            assert_eq!(data.timestamp, 0x19fa3fd4);
+        } else {
+            panic!("Expected MRPTest data");
+        }
+
+        if let MRPTLVData::MRPCommon(data) = &mrp_data.tlv_headers[1].data {
+            assert_eq!(data.sequence_id, 0x057e);
+            assert_eq!(
+                data.domain_uuid,
+                Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap()
+            );
+        } else {
+            panic!("Expected MRPCommon data");
+        }
+
+        if let MRPTLVData::MRPOption(data) = &mrp_data.tlv_headers[2].data {
+            assert_eq!(data.manufacturer_oui, SIEMENS_OUI);
+            assert_eq!(
+                data.sub_tlvs,
+                vec![MRPSubTlv::Ed1 {
+                    ed1_type: 0x00,
+                    manufacturer_data: 0x0000,
+                }]
+            );
+        } else {
+            panic!("Expected MRPOption data");
+        }
+
+        if let MRPTLVData::MRPEnd = &mrp_data.tlv_headers[3].data {
+            // Correct end
+        } else {
+            panic!("Expected MRPEnd data");
+        }
+    }
+
+    #[test]
+    fn test_parse_error_insufficient_data() {
+        assert_eq!(
+            parse_mrp_data(&[0x00]),
+            Err(MRPParseError::InsufficientData {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_truncated_header() {
+        assert_eq!(
+            parse_mrp_data(&[0x00, 0x01, 0x02]),
+            Err(MRPParseError::TruncatedHeader { offset: 2 })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_truncated_tlv_value() {
+        assert_eq!(
+            parse_mrp_data(&[0x00, 0x01, 0x02, 0x12, 0xa0, 0x00]),
+            Err(MRPParseError::TruncatedTlvValue {
+                offset: 2,
+                tlv_type: 0x02,
+                expected: 18,
+                available: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_unknown_tlv_type() {
+        assert_eq!(
+            parse_mrp_data_with_policy(&[0x00, 0x01, 0x42, 0x00], UnknownTlvPolicy::Reject),
+            Err(MRPParseError::UnknownTlvType(0x42))
+        );
+    }
+
+    #[test]
+    fn test_parse_error_short_common() {
+        assert_eq!(
+            parse_mrp_data(&[0x00, 0x01, 0x01, 0x04, 0x05, 0x7e, 0xc3, 0xd6]),
+            Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x01,
+                expected: 18,
+                actual: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let err = MRPParseError::TruncatedTlvValue {
+            offset: 2,
+            tlv_type: 0x02,
+            expected: 18,
+            available: 2,
+        };
+        assert_eq!(
+            err.to_string(),
+            "truncated value for TLV type 0x02 at offset 2: expected 18 bytes, 2 available"
+        );
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_parse_mrp_data_opt() {
+        assert!(parse_mrp_data_opt(&[0x00]).is_none());
+        assert!(parse_mrp_data_opt(&[0x00, 0x01, 0x00, 0x00]).is_some());
+    }
+
+    #[test]
+    fn test_parse_mrp_data_arc() {
+        let buffer = std::sync::Arc::new(test_payload());
+        let parsers: Vec<_> = (0..2)
+            .map(|_| {
+                let buffer = std::sync::Arc::clone(&buffer);
+                std::thread::spawn(move || parse_mrp_data_arc(buffer))
+            })
+            .collect();
+        for parser in parsers {
+            assert_eq!(parser.join().unwrap(), parse_mrp_data(&buffer));
+        }
+        assert_eq!(std::sync::Arc::strong_count(&buffer), 1);
+    }
+
+    #[test]
+    fn test_parse_mrp_data_unwrap() {
+        assert_eq!(
+            parse_mrp_data_unwrap(&test_payload()),
+            parse_mrp_data(&test_payload()).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to parse 1-byte MRP payload: insufficient data")]
+    fn test_parse_mrp_data_unwrap_panics() {
+        parse_mrp_data_unwrap(&[0x00]);
+    }
+
+    fn test_payload() -> Vec<u8> {
+        TEST_PAYLOAD.to_vec()
+    }
+
+    fn test_sa(mrp_data: &MRPData) -> &MacAddress {
+        match &mrp_data.tlv_headers[0].data {
+            MRPTLVData::MRPTest(data) => &data.sa,
+            _ => panic!("Expected MRPTest data"),
+        }
+    }
+
+    #[test]
+    fn test_accessors() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(mrp_data.test().unwrap().prio, 0xa000);
+        assert_eq!(mrp_data.sequence_id(), Some(0x057e));
+        assert_eq!(
+            mrp_data.domain_uuid(),
+            Some(Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap())
+        );
+        assert_eq!(mrp_data.commons().count(), 1);
+        assert_eq!(mrp_data.options().count(), 1);
+        assert_eq!(mrp_data.frame_kind(), MrpFrameKind::Test);
+
+        assert_eq!(
+            parse_mrp_data(&TOPOLOGY_CHANGE_PAYLOAD)
+                .unwrap()
+                .frame_kind(),
+            MrpFrameKind::TopologyChange
+        );
+        assert_eq!(
+            parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap().frame_kind(),
+            MrpFrameKind::LinkChange
+        );
+        assert_eq!(
+            parse_mrp_data(&IN_LINK_DOWN_PAYLOAD).unwrap().frame_kind(),
+            MrpFrameKind::InLinkChange
+        );
+    }
+
+    #[test]
+    fn test_to_fixed_bytes() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let bytes: [u8; 52] = mrp_data.to_fixed_bytes().unwrap();
+        assert_eq!(bytes.to_vec(), test_payload());
+
+        let padded = mrp_data.to_fixed_bytes::<64>().unwrap();
+        assert_eq!(padded[..52], bytes);
+        assert_eq!(padded[52..], [0; 12]);
+
+        let err = mrp_data.to_fixed_bytes::<51>().unwrap_err();
+        assert_eq!(
+            err,
+            EncodeError::BufferTooSmall {
+                needed: 52,
+                available: 51
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "encoded MRP payload is 52 bytes, buffer holds 51"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_option_data_decode_from_reader() {
+        use std::io::Read;
+
+        let payload = test_payload();
+        let expected = parse_mrp_data(&payload).unwrap().options().next().cloned();
+        let mut cursor = std::io::Cursor::new(&payload[44..50]);
+        assert_eq!(
+            MRPOptionData::decode_from_reader(&mut cursor).ok(),
+            expected
+        );
+
+        // Bounded to the TLV's length byte, the End TLV after it is left unread.
+        let mut cursor = std::io::Cursor::new(&payload[44..]);
+        let option = MRPOptionData::decode_from_reader(&mut (&mut cursor).take(6));
+        assert_eq!(option.ok(), expected);
+        assert_eq!(cursor.position(), 6);
+
+        let mut truncated = std::io::Cursor::new(&payload[44..48]);
+        assert_eq!(
+            MRPOptionData::decode_from_reader(&mut truncated),
+            Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x7f,
+                expected: 6,
+                actual: 4,
+            })
+        );
+        let mut truncated = std::io::Cursor::new(&payload[44..46]);
+        assert_eq!(
+            MRPOptionData::decode_from_reader(&mut truncated),
+            Err(MRPParseError::InsufficientData {
+                needed: 3,
+                available: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_option_to_hex_string() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let option = mrp_data.options().next().unwrap();
+        assert_eq!(option.to_hex_string(), "08000600 0000");
+
+        let bare = MRPOptionData::new([0x00, 0x80, 0x63], vec![]);
+        assert_eq!(bare.to_hex_string(), "008063");
+        let ed1 = |manufacturer_data| MRPSubTlv::Ed1 {
+            ed1_type: 0x01,
+            manufacturer_data,
+        };
+        let long = MRPOptionData::new(SIEMENS_OUI, vec![ed1(0xabcd), ed1(0x1234)]);
+        assert_eq!(long.to_hex_string(), "08000601 abcd0112 34");
+    }
+
+    #[test]
+    fn test_manufacturer_name_lookup() {
+        let siemens = MRPOptionData::new(SIEMENS_OUI, vec![]);
+        assert_eq!(siemens.manufacturer_name_lookup(), Cow::Borrowed("Siemens"));
+        let hirschmann = MRPOptionData::new([0x00, 0x80, 0x63], vec![]);
+        assert!(matches!(
+            hirschmann.manufacturer_name_lookup(),
+            Cow::Borrowed("Hirschmann")
+        ));
+        let unknown = MRPOptionData::new([0x02, 0xab, 0xcd], vec![]);
+        let name = unknown.manufacturer_name_lookup();
+        assert!(matches!(name, Cow::Owned(_)));
+        assert_eq!(name, "02:ab:cd");
+    }
+
+    fn health_frames(sequence_ids: &[u16], ring_states: &[RingState]) -> Vec<MRPData> {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        sequence_ids
+            .iter()
+            .zip(ring_states)
+            .map(|(sequence_id, ring_state)| {
+                let mut test = *reference.test().unwrap();
+                test.ring_state = *ring_state;
+                let mut common = *reference.common().unwrap();
+                common.sequence_id = *sequence_id;
+                MRPData::with_all_tlvs(MRP_VERSION, common, test, None).unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ring_state_at() {
+        let mut frames = health_frames(&[1, 2], &[RingState::Open, RingState::Closed]);
+        frames.push(MRPData::with_version_1());
+        assert_eq!(MRPData::ring_state_at(0, &frames), Some(RingState::Open));
+        assert_eq!(MRPData::ring_state_at(1, &frames), Some(RingState::Closed));
+        assert_eq!(MRPData::ring_state_at(2, &frames), None);
+        assert_eq!(MRPData::ring_state_at(3, &frames), None);
+        assert_eq!(MRPData::ring_state_at(0, &[]), None);
+    }
+
+    #[test]
+    fn test_manager_at() {
+        let mut frames = health_frames(&[1, 2], &[RingState::Open, RingState::Closed]);
+        for header in frames[1].iter_mut() {
+            if let MRPTLVData::MRPTest(test) = &mut header.data {
+                test.sa = MacAddress::from([0x00, 0x0e, 0x8c, 0x00, 0x00, 0x01]);
+            }
+        }
+        frames.push(MRPData::with_version_1());
+        assert_eq!(
+            MRPData::manager_at(0, &frames),
+            Some(MacAddress::from([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]))
+        );
+        assert_eq!(
+            MRPData::manager_at(1, &frames),
+            Some(MacAddress::from([0x00, 0x0e, 0x8c, 0x00, 0x00, 0x01]))
+        );
+        assert_eq!(MRPData::manager_at(2, &frames), None);
+        assert_eq!(MRPData::manager_at(3, &frames), None);
+    }
+
+    #[test]
+    fn test_ring_health_score_perfect() {
+        let frames = health_frames(&[0xfffe, 0xffff, 0, 1], &[RingState::Closed; 4]);
+        assert_eq!(MRPData::compute_ring_health_score(&frames), 1.0);
+    }
+
+    #[test]
+    fn test_ring_health_score_degraded() {
+        use RingState::{Closed, Open};
+
+        // 2 of 10 PDUs lost, and the ring was open for one of the 8 that arrived.
+        let frames = health_frames(
+            &[1, 2, 3, 5, 6, 7, 9, 10],
+            &[Closed, Closed, Closed, Open, Closed, Closed, Closed, Closed],
+        );
+        let score = MRPData::compute_ring_health_score(&frames);
+        assert!((score - 7.0 / 8.0 * 0.8).abs() < 1e-12, "{}", score);
+
+        let lossless_open = health_frames(&[1, 2, 3, 4], &[Closed, Open, Open, Closed]);
+        assert_eq!(MRPData::compute_ring_health_score(&lossless_open), 0.5);
+    }
+
+    #[test]
+    fn test_ring_health_score_failed() {
+        let frames = health_frames(&[1, 4, 9, 20], &[RingState::Open; 4]);
+        assert_eq!(MRPData::compute_ring_health_score(&frames), 0.0);
+        assert_eq!(MRPData::compute_ring_health_score(&[]), 0.0);
+
+        // Only topology changes: no Test TLV says the ring is closed.
+        let mut topology_change = MRPData::with_version_1();
+        topology_change
+            .push(MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(
+                0xa000,
+                MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]),
+                10,
+            )))
+            .unwrap();
+        assert_eq!(MRPData::compute_ring_health_score(&[topology_change]), 0.0);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_count_transitions_in_window() {
+        let start = std::time::Instant::now();
+        let frame = |at: u64, ring_state| {
+            let mut payload = test_payload();
+            payload[15] = u16::from(ring_state) as u8;
+            (
+                start + Duration::from_millis(at),
+                parse_mrp_data(&payload).unwrap(),
+            )
+        };
+        let frames = [
+            frame(0, RingState::Closed),
+            frame(20, RingState::Open),
+            frame(200, RingState::Open),
+            frame(260, RingState::Closed),
+            frame(300, RingState::Open),
+        ];
+        let window = Duration::from_millis(100);
+        assert_eq!(MRPData::count_transitions_in_window(&frames, window), 2);
+        assert_eq!(
+            MRPData::count_transitions_in_window(&frames, Duration::from_secs(1)),
+            3
+        );
+        assert_eq!(
+            MRPData::count_transitions_in_window(&frames[..2], window),
+            1
+        );
+        assert_eq!(MRPData::count_transitions_in_window(&[], window), 0);
+
+        // Another domain's ring state is not a transition of this one.
+        let (at, mut other) = frame(310, RingState::Closed);
+        other.tlv_headers[1].data = MRPTLVData::MRPCommon(MRPCommonData::new(1, Uuid::nil()));
+        let mut frames = frames.to_vec();
+        frames.push((at, other));
+        assert_eq!(MRPData::count_transitions_in_window(&frames, window), 2);
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = parse_mrp_data(&test_payload()).unwrap().to_dot();
+        assert!(dot.starts_with("digraph mrp {\n"));
+        assert!(dot.ends_with("}\n"));
+        for line in [
+            "    pdu [label=\"MRP Version: 0x0001\"];",
+            "    tlv0 [label=\"MRP_Test (0x02)\"];",
+            "    pdu -> tlv0;",
+            "    tlv1 [label=\"MRP_Common (0x01)\"];",
+            "    tlv2 [label=\"MRP_Option (0x7f)\"];",
+            "    tlv3 [label=\"MRP_End (0x00)\"];",
+            "    pdu -> tlv3;",
+            "    tlv0_0 [label=\"Prio: 0xa000\"];",
+            "    tlv0 -> tlv0_0;",
+            "    tlv1_1 [label=\"Domain UUID: c3d687fe-789e-03a1-acdb-e5bfcbbc27b6\"];",
+        ] {
+            assert!(dot.lines().any(|l| l == line), "{:?} not in\n{}", line, dot);
+        }
+        assert!(!dot.contains("tlv3_"));
+    }
+
+    #[test]
+    fn test_debug_hex() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(
+            mrp_data.debug_hex().unwrap(),
+            concat!(
+                "# MRP\n",
+                "0000  00 01 02 12 a0 00 00 0e 8c e0 2f 22 00 00 00 00\n",
+                "0010  00 01 19 fa 3f d4 01 12 05 7e c3 d6 87 fe 78 9e\n",
+                "0020  03 a1 ac db e5 bf cb bc 27 b6 7f 06 08 00 06 00\n",
+                "0030  00 00 00 00\n",
+            )
+        );
+        let hex: String = mrp_data
+            .debug_hex()
+            .unwrap()
+            .lines()
+            .skip(1)
+            .map(|line| &line[4..])
+            .collect();
+        assert_eq!(parse_mrp_data_from_hex(&hex).unwrap(), mrp_data);
+        assert_eq!(
+            MRPData::new(MRP_VERSION).debug_hex().unwrap(),
+            "# MRP\n0000  00 01\n"
+        );
+    }
+
+    #[test]
+    fn test_to_dot_nested_sub_tlvs() {
+        let mut mrp_data = MRPData::with_version_1();
+        let manager = MRPManagerData::new(0xa000, MacAddress([0; 6]), 0x8000, MacAddress([1; 6]));
+        mrp_data
+            .push(MRPTLVData::MRPOption(MRPOptionData::new(
+                [0x00, 0x80, 0x63],
+                vec![MRPSubTlv::TestMgrNAck(manager), MRPSubTlv::AutoMgr],
+            )))
+            .unwrap();
+        mrp_data
+            .push(MRPTLVData::Unknown {
+                tlv_type: 0x80,
+                raw: vec![0xaa],
+            })
+            .unwrap();
+        let dot = mrp_data.to_dot();
+        for line in [
+            "    tlv0_1 [label=\"MRP TestMgrNAck\"];",
+            "    tlv0 -> tlv0_1;",
+            "    tlv0_2 [label=\"Prio: 0xa000\"];",
+            "    tlv0_1 -> tlv0_2;",
+            "    tlv0_5 [label=\"Other SA: 01:01:01:01:01:01\"];",
+            "    tlv0_1 -> tlv0_5;",
+            "    tlv0_6 [label=\"MRP AutoMgr\"];",
+            "    tlv0 -> tlv0_6;",
+            "    tlv1 [label=\"Unknown (0x80)\"];",
+            "    tlv1_0 [label=\"Raw: aa\"];",
+        ] {
+            assert!(dot.lines().any(|l| l == line), "{:?} not in\n{}", line, dot);
+        }
+    }
+
+    #[test]
+    fn test_to_canonical_form() {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(reference.to_canonical_form().unwrap(), reference);
+
+        // Option, Common, an unknown TLV and Test, without End.
+        let mut shuffled = MRPData::new(1);
+        for index in [2, 1] {
+            shuffled
+                .push(reference.tlv_headers[index].data.clone())
+                .unwrap();
+        }
+        shuffled
+            .push(MRPTLVData::Unknown {
+                tlv_type: 0x80,
+                raw: vec![0xaa],
+            })
+            .unwrap();
+        shuffled
+            .push(reference.tlv_headers[0].data.clone())
+            .unwrap();
+        assert_ne!(shuffled, reference);
+        assert_eq!(shuffled.to_canonical_form().unwrap(), reference);
+
+        // Extra End TLVs and a stale length byte.
+        let mut messy = reference.clone();
+        messy
+            .tlv_headers
+            .insert(1, MRPTLVHeader::new(MRPTLVData::MRPEnd).unwrap());
+        messy.tlv_headers[0].length = 20;
+        let canonical = messy.to_canonical_form().unwrap();
+        assert_eq!(canonical, reference);
+        assert_eq!(canonical.to_canonical_form().unwrap(), canonical);
+        assert_eq!(canonical.validate(), vec![]);
+    }
+
+    #[test]
+    fn test_to_canonical_form_orders_tlvs_of_one_kind() {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        let MRPTLVData::MRPOption(option) = &reference.tlv_headers[2].data else {
+            panic!("expected an Option TLV");
+        };
+        let mut other = option.clone();
+        other.manufacturer_oui = [0x00, 0x80, 0x63];
+        other.sub_tlvs.clear();
+        let tlvs = [
+            reference.tlv_headers[0].data.clone(),
+            MRPTLVData::MRPTopologyChange(MRPTopologyChangeData {
+                prio: 0x8000,
+                sa: MacAddress([0x00, 0x0e, 0x8c, 0x00, 0x00, 0x01]),
+                interval: 10,
+            }),
+            reference.tlv_headers[1].data.clone(),
+            MRPTLVData::MRPOption(option.clone()),
+            MRPTLVData::MRPOption(other),
+        ];
+        let canonical = |order: &[usize]| {
+            let mut mrp_data = MRPData::new(1);
+            for &index in order {
+                mrp_data.push(tlvs[index].clone()).unwrap();
+            }
+            mrp_data.to_canonical_form().unwrap()
+        };
+        let forward = canonical(&[0, 1, 2, 3, 4]);
+        assert_eq!(forward, canonical(&[1, 0, 2, 4, 3]));
+        let types: Vec<u8> = forward.iter().map(|header| header.tlv_type).collect();
+        assert_eq!(types, [0x02, 0x03, 0x01, 0x7f, 0x7f, 0x00]);
+    }
+
+    #[test]
+    fn test_replace_typed_tlvs() {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        let mut mrp_data = reference.clone();
+        let old_common = *mrp_data.common().unwrap();
+        let new_common = &old_common + 1;
+        assert_eq!(mrp_data.replace_common_tlv(new_common), Some(old_common));
+        assert_eq!(mrp_data.common(), Some(&new_common));
+        assert_eq!(mrp_data.tlv_headers.len(), 4);
+
+        let old_test = *mrp_data.test().unwrap();
+        let new_test = MRPTestData {
+            ring_state: RingState::Closed,
+            transition: 2,
+            ..old_test
+        };
+        assert_eq!(mrp_data.replace_test_tlv(new_test), Some(old_test));
+        assert_eq!(mrp_data.test(), Some(&new_test));
+
+        let new_option = MRPOptionData::new([0x00, 0x80, 0x63], vec![MRPSubTlv::AutoMgr]);
+        let old_option = mrp_data
+            .replace_option_tlv(new_option.clone())
+            .unwrap()
+            .unwrap();
+        assert_eq!(old_option.manufacturer_oui, SIEMENS_OUI);
+        assert_eq!(mrp_data.options().next(), Some(&new_option));
+        assert_eq!(mrp_data.validate(), vec![]);
+        assert!(mrp_data.iter().all(MRPTLVHeader::is_consistent));
+
+        assert_eq!(
+            mrp_data
+                .replace_tlv(reference.tlv_headers[2].data.clone())
+                .unwrap(),
+            Some(MRPTLVData::MRPOption(new_option))
+        );
+    }
+
+    #[test]
+    fn test_replace_inserts_missing_tlvs_in_wire_order() {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        let mut mrp_data = MRPData::with_version_1();
+        mrp_data.push(MRPTLVData::MRPEnd).unwrap();
+        let common = *reference.common().unwrap();
+        let test = *reference.test().unwrap();
+        let option = reference.options().next().unwrap().clone();
+        assert_eq!(mrp_data.replace_option_tlv(option).unwrap(), None);
+        assert_eq!(mrp_data.replace_common_tlv(common), None);
+        assert_eq!(mrp_data.replace_test_tlv(test), None);
+        assert_eq!(mrp_data, reference);
+    }
+
+    #[test]
+    fn test_first_and_last_tlv() {
+        let empty = MRPData::with_version_1();
+        assert_eq!(empty.first_tlv(), None);
+        assert_eq!(empty.last_tlv(), None);
+
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert!(matches!(
+            mrp_data.first_tlv().map(|header| &header.data),
+            Some(MRPTLVData::MRPTest(_))
+        ));
+        assert_eq!(
+            mrp_data.last_tlv().map(|header| &header.data),
+            Some(&MRPTLVData::MRPEnd)
+        );
+
+        let mut single = MRPData::with_version_1();
+        single.push(MRPTLVData::MRPEnd).unwrap();
+        assert_eq!(single.first_tlv(), single.last_tlv());
+    }
+
+    #[test]
+    fn test_with_version_1() {
+        let mrp_data = MRPData::with_version_1();
+        assert_eq!(mrp_data.version, 0x0001);
+        assert_eq!(mrp_data, MRPData::new(MRP_VERSION));
+    }
+
+    #[test]
+    fn test_with_all_tlvs() {
+        let reference = parse_mrp_data(&test_payload()).unwrap();
+        let mrp_data = MRPData::with_all_tlvs(
+            MRP_VERSION,
+            *reference.common().unwrap(),
+            *reference.test().unwrap(),
+            reference.options().next().cloned(),
+        )
+        .unwrap();
+        assert_eq!(mrp_data, reference);
+        assert_eq!(mrp_data.to_bytes().unwrap(), test_payload());
+        assert_eq!(
+            parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap(),
+            mrp_data
+        );
+
+        let without_option = MRPData::with_all_tlvs(
+            MRP_VERSION,
+            *reference.common().unwrap(),
+            *reference.test().unwrap(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(without_option.tlv_headers.len(), 3);
+        assert_eq!(without_option.validate(), vec![]);
+        assert_eq!(
+            parse_mrp_data(&without_option.to_bytes().unwrap()).unwrap(),
+            without_option
+        );
+    }
+
+    #[test]
+    fn test_accessors_degenerate_frame() {
+        // Two Commons and an Option: no frame TLV and no End.
+        let mut mrp_data = MRPData::new(1);
+        let first = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        mrp_data
+            .push(MRPTLVData::MRPCommon(MRPCommonData::new(1, first)))
+            .unwrap();
+        mrp_data
+            .push(MRPTLVData::MRPCommon(MRPCommonData::new(2, Uuid::nil())))
+            .unwrap();
+        mrp_data
+            .push(MRPTLVData::MRPOption(MRPOptionData::new(
+                [0x00, 0x80, 0x63],
+                vec![],
+            )))
+            .unwrap();
+        assert_eq!(mrp_data.sequence_id(), Some(1));
+        assert_eq!(mrp_data.domain_uuid(), Some(first));
+        assert_eq!(
+            mrp_data
+                .commons()
+                .map(|common| common.sequence_id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(mrp_data.test(), None);
+        assert_eq!(mrp_data.frame_kind(), MrpFrameKind::Unknown);
+
+        let mrp_data = parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap();
+        assert_eq!(mrp_data.options().count(), 1);
+        assert_eq!(MRPData::new(1).frame_kind(), MrpFrameKind::Unknown);
+        assert_eq!(MRPData::new(1).common(), None);
+    }
+
+    #[test]
+    fn test_domain_matches_any() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let domain = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        assert!(!mrp_data.domain_matches_any(&[]));
+        assert!(mrp_data.domain_matches_any(&[domain]));
+        assert!(mrp_data.domain_matches_any(&[Uuid::nil(), domain]));
+        assert!(!mrp_data.domain_matches_any(&[Uuid::nil(), Uuid::max()]));
+    }
+
+    #[test]
+    fn test_apply_sa_remapping_identity() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.apply_sa_remapping(|sa| MacAddress(sa.0));
+        assert_eq!(parse_mrp_data(&test_payload()).unwrap(), mrp_data);
+    }
+
+    #[test]
+    fn test_apply_sa_remapping_zero_nic_octets() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.apply_sa_remapping(|sa| MacAddress([sa.0[0], sa.0[1], sa.0[2], 0, 0, 0]));
+        assert_eq!(test_sa(&mrp_data).to_string(), "00:0e:8c:00:00:00");
+    }
+
+    // Synthetic MRP_TopologyChange PDU built to IEC 62439-2 (no Ethernet padding), reusing the
+    // reference payload's domain UUID: TopologyChange, Common, End.
+    const TOPOLOGY_CHANGE_PAYLOAD: [u8; 36] = [
+        0x00, 0x01, 0x03, 0x0a, 0x80, 0x00, 0x00, 0x1b, 0x1b, 0x6c, 0x4e, 0x10, 0x00, 0x14, 0x01,
+        0x12, 0x3a, 0x0c, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb, 0xe5, 0xbf,
+        0xcb, 0xbc, 0x27, 0xb6, 0x00, 0x00,
+    ];
+
+    // Synthetic MRP_LinkDown PDU for a secondary port losing link, built the same way:
+    // LinkDown, Common, End.
+    const LINK_DOWN_PAYLOAD: [u8; 38] = [
+        0x00, 0x01, 0x04, 0x0c, 0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21, 0x00, 0x01, 0x00, 0x14, 0x00,
+        0x01, 0x01, 0x12, 0x00, 0x2f, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb,
+        0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_parse_topology_change() {
+        let mrp_data = parse_mrp_data(&TOPOLOGY_CHANGE_PAYLOAD).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 3);
+        if let MRPTLVData::MRPTopologyChange(data) = &mrp_data.tlv_headers[0].data {
+            assert_eq!(data.prio, 0x8000);
+            assert_eq!(data.sa.to_string(), "00:1b:1b:6c:4e:10");
+            assert_eq!(data.interval, 0x0014);
+        } else {
+            panic!("Expected MRPTopologyChange data");
+        }
+        if let MRPTLVData::MRPCommon(data) = &mrp_data.tlv_headers[1].data {
+            assert_eq!(data.sequence_id, 0x3a0c);
+        } else {
+            panic!("Expected MRPCommon data");
+        }
+    }
+
+    #[test]
+    fn test_parse_link_down() {
+        let mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 3);
+        if let MRPTLVData::MRPLinkDown(data) = &mrp_data.tlv_headers[0].data {
+            assert_eq!(data.sa.to_string(), "00:1b:1b:6c:52:21");
+            assert_eq!(data.port_role, PortRole::Secondary);
+            assert_eq!(data.interval, 0x0014);
+            assert_eq!(data.blocked, 0x0001);
+        } else {
+            panic!("Expected MRPLinkDown data");
+        }
+    }
+
+    #[test]
+    fn test_parse_link_up() {
+        let mut payload = LINK_DOWN_PAYLOAD;
+        payload[2] = 0x05;
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        assert!(matches!(
+            mrp_data.tlv_headers[0].data,
+            MRPTLVData::MRPLinkUp(MRPLinkData {
+                port_role: PortRole::Secondary,
+                ..
+            })
+        ));
+    }
+
+    // Synthetic MRP_InTest PDU built to IEC 62439-2 (no Ethernet padding), reusing the reference
+    // payload's domain UUID: InTest, Common, End.
+    const IN_TEST_PAYLOAD: [u8; 44] = [
+        0x00, 0x01, 0x06, 0x12, 0x00, 0x01, 0x00, 0x1b, 0x1b, 0x6c, 0x4e, 0x10, 0x00, 0x02, 0x00,
+        0x01, 0x00, 0x03, 0x00, 0x2f, 0x5a, 0x10, 0x01, 0x12, 0x00, 0x07, 0xc3, 0xd6, 0x87, 0xfe,
+        0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb, 0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6, 0x00, 0x00,
+    ];
+
+    // Synthetic MRP_InLinkDown PDU for an interconnection port losing link, built the same way:
+    // InLinkDown, Common, End.
+    const IN_LINK_DOWN_PAYLOAD: [u8; 40] = [
+        0x00, 0x01, 0x08, 0x0e, 0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21, 0x00, 0x02, 0x00, 0x01, 0x00,
+        0x14, 0x00, 0x00, 0x01, 0x12, 0x00, 0x30, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1,
+        0xac, 0xdb, 0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_parse_in_test() {
+        let mrp_data = parse_mrp_data(&IN_TEST_PAYLOAD).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 3);
+        assert_eq!(
+            mrp_data.in_test(),
+            Some(&MRPInTestData::new(
+                0x0001,
+                MacAddress([0x00, 0x1b, 0x1b, 0x6c, 0x4e, 0x10]),
+                PortRole::Interconnection,
+                RingState::Closed,
+                0x0003,
+                0x002f_5a10,
+            ))
+        );
+        assert!(mrp_data.to_string().contains(
+            "    MRP InTest Data:\n      InID: 0x0001\n      SA: 00:1b:1b:6c:4e:10\n      Port Role: 0x0002 (Interconnection)\n      InState: 0x0001 (Closed)\n"
+        ));
+        assert_eq!(mrp_data.to_bytes().unwrap(), IN_TEST_PAYLOAD);
+        assert_eq!(
+            transmit::suggested_destination(&mrp_data),
+            Some(transmit::MC_INTEST)
+        );
+    }
+
+    #[test]
+    fn test_parse_in_link_down() {
+        let mrp_data = parse_mrp_data(&IN_LINK_DOWN_PAYLOAD).unwrap();
+        assert!(matches!(
+            mrp_data.tlv_headers[0].data,
+            MRPTLVData::MRPInLinkDown(_)
+        ));
+        assert_eq!(
+            mrp_data.in_link(),
+            Some(&MRPInLinkData::new(
+                MacAddress([0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]),
+                PortRole::Interconnection,
+                0x0001,
+                0x0014,
+                0x0000,
+            ))
+        );
+        assert_eq!(mrp_data.in_test(), None);
+        assert!(mrp_data.to_string().contains(
+            "    MRP InLinkDown Data:\n      SA: 00:1b:1b:6c:52:21\n      Port Role: 0x0002 (Interconnection)\n      InID: 0x0001\n      Interval: 0x0014 (20 ms)\n      LinkInfo: 0x0000\n"
+        ));
+        assert_eq!(mrp_data.to_bytes().unwrap(), IN_LINK_DOWN_PAYLOAD);
+        assert_eq!(transmit::pre_transmit_check(&mrp_data), Ok(()));
+        assert_eq!(
+            transmit::suggested_destination(&mrp_data),
+            Some(transmit::MC_INCONTROL)
+        );
+    }
+
+    #[test]
+    fn test_parse_short_topology_change_and_link() {
+        assert_eq!(
+            parse_mrp_data(&[0x00, 0x01, 0x03, 0x02, 0x80, 0x00]),
+            Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x03,
+                expected: 10,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            parse_mrp_data(&[0x00, 0x01, 0x05, 0x06, 0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]),
+            Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x05,
+                expected: 12,
+                actual: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_display_topology_change_and_link() {
+        let mrp_data = parse_mrp_data(&TOPOLOGY_CHANGE_PAYLOAD).unwrap();
+        assert!(mrp_data
+            .to_string()
+            .contains("    MRP TopologyChange Data:\n      Prio: 0x8000\n      SA: 00:1b:1b:6c:4e:10\n      Interval: 0x0014 (20 ms)\n"));
+        let mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        assert!(mrp_data.to_string().contains(
+            "    MRP LinkDown Data:\n      SA: 00:1b:1b:6c:52:21\n      Port Role: 0x0001 (Secondary)\n"
+        ));
+    }
+
+    #[test]
+    fn test_iter_mut_sets_lengths() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        for header in mrp_data.iter_mut() {
+            header.length = 0xff;
+        }
+        for header in mrp_data.iter_mut() {
+            header.length = header.data.payload_length() as u8;
+        }
+        let lengths: Vec<u8> = mrp_data.iter().map(|header| header.length).collect();
+        assert_eq!(lengths, vec![18, 18, 6, 0]);
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+    }
+
+    #[test]
+    fn test_parse_unknown_tlv_between_known() {
+        let mut payload = test_payload();
+        // Vendor TLV 0x80 with three value bytes between Common and Option.
+        payload.splice(42..42, [0x80, 0x03, 0xde, 0xad, 0x01]);
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 5);
+        assert_eq!(mrp_data.tlv_headers[2].tlv_type, 0x80);
+        assert_eq!(mrp_data.tlv_headers[2].length, 3);
+        assert_eq!(
+            mrp_data.tlv_headers[2].data,
+            MRPTLVData::Unknown {
+                tlv_type: 0x80,
+                raw: vec![0xde, 0xad, 0x01]
+            }
+        );
+        assert!(matches!(
+            mrp_data.tlv_headers[3].data,
+            MRPTLVData::MRPOption(_)
+        ));
+        assert!(matches!(mrp_data.tlv_headers[4].data, MRPTLVData::MRPEnd));
+        assert!(mrp_data
+            .to_string()
+            .contains("    Unknown TLV Data (0x80):\n      Raw: de ad 01\n"));
+
+        assert_eq!(
+            parse_mrp_data_with_policy(&payload, UnknownTlvPolicy::Reject),
+            Err(MRPParseError::UnknownTlvType(0x80))
+        );
+    }
+
+    #[test]
+    fn test_is_consistent() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert!(mrp_data.iter().all(MRPTLVHeader::is_consistent));
+
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.tlv_headers[0].length = 20;
+        assert!(!mrp_data.tlv_headers[0].is_consistent());
+        mrp_data.tlv_headers[1].tlv_type = 0x02;
+        assert!(!mrp_data.tlv_headers[1].is_consistent());
+        assert!(mrp_data.tlv_headers[2].is_consistent());
+    }
+
+    #[test]
+    fn test_mrp_tlv_type_conversions() {
+        for tlv_type in MrpTlvType::ALL {
+            assert_eq!(MrpTlvType::try_from(u8::from(tlv_type)), Ok(tlv_type));
+        }
+        assert_eq!(MrpTlvType::try_from(0x7f), Ok(MrpTlvType::Option));
+        assert_eq!(MrpTlvType::try_from(0x42), Err(0x42));
+    }
+
+    #[test]
+    fn test_parse_ignores_padding_after_end() {
+        for padding in 8..=14 {
+            let mut payload = test_payload();
+            payload.extend(std::iter::repeat_n(0x00, padding));
+            let mrp_data = parse_mrp_data(&payload).unwrap();
+            assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_with_warnings() {
+        let (result, warnings) = parse_mrp_data_with_warnings(&test_payload());
+        assert_eq!(result, parse_mrp_data(&test_payload()));
+        assert_eq!(warnings, vec![]);
+
+        let mut padded = test_payload();
+        padded.extend([0x00; 8]);
+        let (result, warnings) = parse_mrp_data_with_warnings(&padded);
+        assert_eq!(result, parse_mrp_data(&test_payload()));
+        assert_eq!(warnings, vec![ParseWarning::PaddingBytesIgnored]);
+
+        let mut unknown = test_payload();
+        unknown.splice(42..42, [0x80, 0x02, 0xde, 0xad]);
+        let (result, warnings) = parse_mrp_data_with_warnings(&unknown);
+        assert_eq!(result, parse_mrp_data(&test_payload()));
+        assert_eq!(warnings, vec![ParseWarning::UnknownTlvSkipped(0x80)]);
+
+        // Common TLV claiming 20 bytes instead of 18.
+        let mut long = test_payload();
+        long[23] = 0x14;
+        long.splice(42..42, [0x00, 0x00]);
+        let (result, warnings) = parse_mrp_data_with_warnings(&long);
+        assert_eq!(result.unwrap().sequence_id(), Some(0x057e));
+        assert_eq!(
+            warnings,
+            vec![ParseWarning::LengthFieldMismatch { tlv_type: 0x01 }]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "length field of TLV type 0x01 does not match its value"
+        );
+
+        let (result, warnings) = parse_mrp_data_with_warnings(&unknown[..44]);
+        assert!(matches!(
+            result,
+            Err(MRPParseError::TruncatedTlvValue { .. })
+        ));
+        assert_eq!(warnings, vec![]);
+    }
+
+    #[test]
+    fn test_parse_ignores_junk_after_end() {
+        let mut payload = test_payload();
+        payload.extend([0x02, 0xff, 0xde, 0xad, 0xbe, 0xef]);
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 4);
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+    }
+
+    #[test]
+    fn test_fix_lengths() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.tlv_headers[0].length = 4;
+        mrp_data.tlv_headers[2].length = 0;
+        mrp_data.fix_lengths().unwrap();
+        assert_eq!(mrp_data.tlv_headers[0].length, 18);
+        assert_eq!(mrp_data.tlv_headers[2].length, 6);
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+    }
+
+    fn assert_round_trip(mrp_data: &MRPData) {
+        assert_eq!(
+            &parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap(),
+            mrp_data
+        );
+    }
+
+    #[test]
+    fn test_to_bytes_reference_payload() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(mrp_data.to_bytes().unwrap(), test_payload());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_write_to() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let mut written = Vec::new();
+        mrp_data.write_to(&mut written).unwrap();
+        assert_eq!(written, test_payload());
+    }
+
+    #[test]
+    fn test_over_long_values_are_rejected() {
+        let unknown = MRPTLVData::Unknown {
+            tlv_type: 0x80,
+            raw: vec![0; 256],
+        };
+        let too_long = EncodeError::ValueTooLong {
+            tlv_type: 0x80,
+            length: 256,
+        };
+        assert_eq!(MRPTLVHeader::new(unknown.clone()), Err(too_long));
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(mrp_data.push(unknown.clone()), Err(too_long));
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+        assert_eq!(
+            too_long.to_string(),
+            "TLV type 0x80 value is 256 bytes, at most 255 can be encoded"
+        );
+
+        // Values built or deserialized directly bypass push; encoding still refuses them.
+        let option = MRPOptionData::new(
+            [0x00, 0x80, 0x63],
+            vec![MRPSubTlv::Raw {
+                sub_type: 0x80,
+                value: vec![0; 251],
+            }],
+        );
+        assert_eq!(option.payload_length(), 256);
+        mrp_data.tlv_headers[2].data = MRPTLVData::MRPOption(option);
+        let too_long = EncodeError::ValueTooLong {
+            tlv_type: 0x7f,
+            length: 256,
+        };
+        assert_eq!(mrp_data.to_bytes(), Err(too_long));
+        assert_eq!(mrp_data.fix_lengths(), Err(too_long));
+        assert_eq!(mrp_data.tlv_headers[2].length, 6);
+        assert_eq!(mrp_data.to_canonical_form(), Err(too_long));
+        #[cfg(feature = "std")]
+        {
+            let mut written = Vec::new();
+            let err = mrp_data.write_to(&mut written).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+            assert!(written.is_empty());
+        }
+
+        // 255 bytes is the longest value a length byte can describe.
+        let mut mrp_data = MRPData::with_version_1();
+        mrp_data
+            .push(MRPTLVData::Unknown {
+                tlv_type: 0x80,
+                raw: vec![0; 255],
+            })
+            .unwrap();
+        assert_round_trip(&mrp_data);
+    }
+
+    #[test]
+    fn test_round_trip_every_variant() {
+        let uuid = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        for n in [0x0000u16, 0x0001, 0x7fff, 0x8000, 0xa000, 0xffff] {
+            let [hi, lo] = n.to_be_bytes();
+            let sa = || MacAddress([0x00, 0x0e, 0x8c, hi, lo, 0x22]);
+            let mut mrp_data = MRPData::new(n);
+            mrp_data
+                .push(MRPTLVData::MRPTest(MRPTestData::new(
+                    n,
+                    sa(),
+                    n.into(),
+                    n.into(),
+                    n,
+                    u32::from(n) << 16 | 0x3fd4,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(
+                    n,
+                    sa(),
+                    n,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPLinkDown(MRPLinkData::new(
+                    sa(),
+                    n.into(),
+                    n,
+                    n,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPLinkUp(MRPLinkData::new(
+                    sa(),
+                    n.into(),
+                    n,
+                    n,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPInTest(MRPInTestData::new(
+                    n,
+                    sa(),
+                    n.into(),
+                    n.into(),
+                    n,
+                    u32::from(n) << 8,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPInTopologyChange(
+                    MRPInTopologyChangeData::new(sa(), n, n),
+                ))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPInLinkDown(MRPInLinkData::new(
+                    sa(),
+                    n.into(),
+                    n,
+                    n,
+                    n,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPInLinkUp(MRPInLinkData::new(
+                    sa(),
+                    n.into(),
+                    n,
+                    n,
+                    n,
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPInLinkStatusPoll(
+                    MRPInLinkStatusPollData::new(sa(), n.into(), n),
+                ))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPCommon(MRPCommonData::new(n, uuid)))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPOption(MRPOptionData::new(
+                    SIEMENS_OUI,
+                    vec![
+                        MRPSubTlv::Ed1 {
+                            ed1_type: lo,
+                            manufacturer_data: n,
+                        },
+                        MRPSubTlv::TestMgrNAck(MRPManagerData::new(n, sa(), !n, sa())),
+                        MRPSubTlv::AutoMgr,
+                    ],
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::MRPOption(MRPOptionData::new(
+                    [0x00, 0x80, hi],
+                    vec![
+                        MRPSubTlv::TestPropagate(MRPManagerData::new(n, sa(), n, sa())),
+                        MRPSubTlv::Raw {
+                            sub_type: 0x80,
+                            value: vec![lo; usize::from(hi % 8)],
+                        },
+                    ],
+                )))
+                .unwrap();
+            mrp_data
+                .push(MRPTLVData::Unknown {
+                    tlv_type: 0x80,
+                    raw: vec![hi; usize::from(lo % 8)],
+                })
+                .unwrap();
+            mrp_data.push(MRPTLVData::MRPEnd).unwrap();
+            assert!(mrp_data.iter().all(MRPTLVHeader::is_consistent));
+            assert_round_trip(&mrp_data);
+        }
+    }
+
+    #[test]
+    fn test_tlvs_only_round_trip() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let tlvs = mrp_data.serialize_tlvs_only().unwrap();
+        let mut bytes = mrp_data.version.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&tlvs);
+        assert_eq!(bytes, mrp_data.to_bytes().unwrap());
+        assert_eq!(parse_mrp_tlvs_only(&tlvs).unwrap(), mrp_data.tlv_headers);
+    }
+
+    #[test]
+    fn test_to_bytes_derives_length() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.tlv_headers[0].length = 0;
+        assert_eq!(mrp_data.to_bytes().unwrap(), test_payload());
+    }
+
+    #[test]
+    fn test_fix_types() {
+        let mut mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        mrp_data.tlv_headers[0].tlv_type = 0x01;
+        mrp_data.tlv_headers[3].tlv_type = 0x7f;
+        mrp_data.fix_types();
+        assert_eq!(mrp_data.tlv_headers[0].tlv_type, 0x02);
+        assert_eq!(mrp_data.tlv_headers[3].tlv_type, 0x00);
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+    }
+
+    #[test]
+    fn test_try_parse_helpers() {
+        assert_eq!(try_parse_u16(&[0x12, 0x34, 0x56]), Ok(0x1234));
+        assert_eq!(
+            try_parse_u16(&[0x12]),
+            Err(MRPParseError::InsufficientData {
+                needed: 2,
+                available: 1
+            })
+        );
+        assert_eq!(try_parse_u32(&[0x12, 0x34, 0x56, 0x78]), Ok(0x12345678));
+        assert!(try_parse_u32(&[0x12, 0x34, 0x56]).is_err());
+        assert_eq!(
+            try_parse_mac_address(&[0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]),
+            Ok(MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]))
+        );
+        assert!(try_parse_mac_address(&[0x00, 0x0e, 0x8c]).is_err());
+        assert!(MacAddress::try_from(&[0x00, 0x0e, 0x8c, 0xe0, 0x2f][..]).is_err());
+        assert!(MacAddress::try_from(&[0u8; 7][..]).is_err());
+        assert_eq!(MacAddress::from([0u8; 6]), MacAddress([0u8; 6]));
+    }
+
+    #[test]
+    fn test_truncated_tlv_bodies() {
+        for (tlv_type, minimum) in [
+            (0x02, 18),
+            (0x01, 18),
+            (0x03, 10),
+            (0x04, 12),
+            (0x05, 12),
+            (0x06, 18),
+            (0x07, 10),
+            (0x08, 14),
+            (0x09, 14),
+            (0x0a, 10),
+            (0x7f, 3),
+        ] {
+            for length in 0..minimum {
+                let mut payload = vec![0x00, 0x01, tlv_type, length as u8];
+                payload.extend(std::iter::repeat_n(0xaa, length));
+                assert_eq!(
+                    parse_mrp_data(&payload),
+                    Err(MRPParseError::InvalidTlvLength {
+                        tlv_type,
+                        expected: minimum,
+                        actual: length
+                    }),
+                    "TLV type {:#04x} with length {}",
+                    tlv_type,
+                    length
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_option_sub_tlvs() {
+        // Non-Siemens OUI with TestMgrNAck, AutoMgr and a vendor blob.
+        let mut payload = vec![0x00, 0x01, 0x7f, 29, 0x00, 0x80, 0x63];
+        payload.extend_from_slice(&[0x01, 16, 0x80, 0x00]);
+        payload.extend_from_slice(&[0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        payload.extend_from_slice(&[0xa0, 0x00, 0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]);
+        payload.extend_from_slice(&[0x03, 0, 0xf0, 4, 0xde, 0xad, 0xbe, 0xef]);
+        payload.extend_from_slice(&[0x00, 0x00]);
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        let MRPTLVData::MRPOption(option) = &mrp_data.tlv_headers[0].data else {
+            panic!("Expected MRPOption data");
+        };
+        assert_eq!(option.manufacturer_oui, [0x00, 0x80, 0x63]);
+        assert_eq!(
+            option.sub_tlvs,
+            vec![
+                MRPSubTlv::TestMgrNAck(MRPManagerData::new(
+                    0x8000,
+                    MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]),
+                    0xa000,
+                    MacAddress([0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]),
+                )),
+                MRPSubTlv::AutoMgr,
+                MRPSubTlv::Raw {
+                    sub_type: 0xf0,
+                    value: vec![0xde, 0xad, 0xbe, 0xef],
+                },
+            ]
+        );
+        assert_eq!(mrp_data.to_bytes().unwrap(), payload);
+        let text = mrp_data.to_string();
+        assert!(text.contains("      MRP TestMgrNAck:\n        Prio: 0x8000\n"));
+        assert!(text.contains("        Other SA: 00:1b:1b:6c:52:21\n      MRP AutoMgr\n"));
+        assert!(text.contains("      Unknown Sub-TLV (0xf0):\n        Raw: de ad be ef\n"));
+    }
+
+    #[test]
+    fn test_parse_option_sub_tlv_errors() {
+        // Sub-TLV header claims more bytes than the Option value holds.
+        let payload = [0x00, 0x01, 0x7f, 0x06, 0x00, 0x80, 0x63, 0x01, 0x10, 0x00];
+        assert_eq!(
+            parse_mrp_data(&payload),
+            Err(MRPParseError::TruncatedSubTlv { offset: 3 })
+        );
+        // Dangling single byte after the OUI.
+        let payload = [0x00, 0x01, 0x7f, 0x04, 0x00, 0x80, 0x63, 0x03];
+        assert_eq!(
+            parse_mrp_data(&payload),
+            Err(MRPParseError::TruncatedSubTlv { offset: 3 })
+        );
+        // AutoMgr must be empty.
+        let payload = [0x00, 0x01, 0x7f, 0x06, 0x00, 0x80, 0x63, 0x03, 0x01, 0x00];
+        assert_eq!(
+            parse_mrp_data(&payload),
+            Err(MRPParseError::InvalidSubTlvLength {
+                sub_type: 0x03,
+                expected: 0,
+                actual: 1,
+            })
+        );
+        // A Siemens option needs the full Ed1 header.
+        let payload = [0x00, 0x01, 0x7f, 0x04, 0x08, 0x00, 0x06, 0x00];
+        assert_eq!(
+            parse_mrp_data(&payload),
+            Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x7f,
+                expected: 6,
+                actual: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parser_default_matches_parse_mrp_data() {
+        let payload = test_payload();
+        assert_eq!(MRPParser::new().parse(&payload), parse_mrp_data(&payload));
+        assert_eq!(
+            MRPParser::new().parse(&payload[..30]),
+            parse_mrp_data(&payload[..30])
+        );
+    }
+
+    #[test]
+    fn test_parser_policy() {
+        let payload = [0x00, 0x01, 0x80, 0x01, 0xaa, 0x00, 0x00];
+        assert!(MRPParser::new().parse(&payload).is_ok());
+        assert_eq!(
+            MRPParser::new()
+                .policy(UnknownTlvPolicy::Reject)
+                .parse(&payload),
+            Err(MRPParseError::UnknownTlvType(0x80))
+        );
+    }
+
+    #[test]
+    fn test_parser_max_tlvs() {
+        let payload = test_payload();
+        assert!(MRPParser::new().max_tlvs(4).parse(&payload).is_ok());
+        assert_eq!(
+            MRPParser::new().max_tlvs(3).parse(&payload),
+            Err(MRPParseError::TooManyTlvs { max: 3 })
+        );
+    }
+
+    #[test]
+    fn test_parser_strict_version() {
+        let mut payload = test_payload();
+        assert!(MRPParser::new()
+            .strict_version(true)
+            .parse(&payload)
+            .is_ok());
+        payload[1] = 0x02;
+        assert!(MRPParser::new().parse(&payload).is_ok());
+        assert_eq!(
+            MRPParser::new().strict_version(true).parse(&payload),
+            Err(MRPParseError::UnsupportedVersion(0x0002))
+        );
+    }
+
+    #[test]
+    fn test_parser_strict_end() {
+        let payload = test_payload();
+        let without_end = &payload[..payload.len() - 2];
+        assert!(MRPParser::new().strict_end(true).parse(&payload).is_ok());
+        assert!(MRPParser::new().parse(without_end).is_ok());
+        assert_eq!(
+            MRPParser::new().strict_end(true).parse(without_end),
+            Err(MRPParseError::MissingEnd)
+        );
+    }
+
+    #[test]
+    fn test_parser_custom_logger() {
+        use std::sync::Mutex;
+
+        let messages = Arc::new(Mutex::new(Vec::<String>::new()));
+        let sink = Arc::clone(&messages);
+        let parser = MRPParser::new()
+            .strict_end(true)
+            .with_custom_logger(move |message| sink.lock().unwrap().push(message.into()));
+
+        let payload = test_payload();
+        assert_eq!(parser.parse(&payload), parse_mrp_data(&payload));
+        assert_eq!(
+            *messages.lock().unwrap(),
+            vec![
+                "52-byte PDU, version 0x0001",
+                "TLV type 0x02, length 18 at offset 2",
+                "TLV type 0x01, length 18 at offset 22",
+                "TLV type 0x7f, length 6 at offset 42",
+                "TLV type 0x00, length 0 at offset 50",
+                "parsed 4 TLVs",
+            ]
+        );
+
+        messages.lock().unwrap().clear();
+        assert!(parser.clone().parse(&payload[..50]).is_err());
+        assert_eq!(
+            messages.lock().unwrap().last().map(String::as_str),
+            Some("parse failed: MRP payload does not end with MRP_End")
+        );
+        assert!(format!("{:?}", parser).contains("logger: Some(\"..\")"));
+    }
+
+    #[test]
+    fn test_parser_is_shareable_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MRPParser>();
+
+        let parser = MRPParser::new().with_custom_logger(|_| {});
+        let payload = test_payload();
+        std::thread::scope(|scope| {
+            scope.spawn(|| assert!(parser.parse(&payload).is_ok()));
+        });
+        assert_eq!(parser.clone(), parser);
+        assert_ne!(MRPParser::new().with_custom_logger(|_| {}), parser);
+        assert_eq!(MRPParser::new(), MRPParser::default());
+        assert_ne!(MRPParser::new().strict_end(true), MRPParser::new());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_parse_mrp_data_from_env() {
+        let hex: String = test_payload()
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        std::env::set_var("MRP_TEST_PAYLOAD_HEX", &hex);
+        assert_eq!(
+            parse_mrp_data_from_env("MRP_TEST_PAYLOAD_HEX"),
+            parse_mrp_data(&test_payload())
+        );
+
+        std::env::set_var("MRP_TEST_PAYLOAD_BAD_HEX", &hex[1..]);
+        assert_eq!(
+            parse_mrp_data_from_env("MRP_TEST_PAYLOAD_BAD_HEX"),
+            Err(MRPParseError::InvalidHex)
+        );
+        assert_eq!(
+            parse_mrp_data_from_env("MRP_TEST_PAYLOAD_UNSET"),
+            Err(MRPParseError::EnvVarNotFound(
+                "MRP_TEST_PAYLOAD_UNSET".into()
+            ))
+        );
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_parse_mrp_frame_from_unix_socket() {
+        use std::os::unix::net::UnixDatagram;
+
+        let dir = std::env::temp_dir().join(format!("mrp-socket-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mrp.sock");
+        let target = path.clone();
+        let sender = std::thread::spawn(move || {
+            let socket = UnixDatagram::unbound().unwrap();
+            // Retry until the receiver has bound the socket.
+            while socket.connect(&target).is_err() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            socket.send(&test_payload()).unwrap();
+        });
+        assert_eq!(
+            parse_mrp_frame_from_unix_socket(&path),
+            parse_mrp_data(&test_payload())
+        );
+        sender.join().unwrap();
+        assert!(!path.exists());
+
+        assert_eq!(
+            parse_mrp_frame_from_unix_socket(&dir.join("missing").join("mrp.sock")),
+            Err(MRPParseError::Io(std::io::ErrorKind::NotFound))
+        );
+        std::fs::remove_dir(&dir).unwrap();
+    }
+
+    #[cfg(all(unix, feature = "std"))]
+    #[test]
+    fn test_parse_mrp_frames_from_fifo() {
+        use std::io::Write;
+
+        let dir = std::env::temp_dir().join(format!("mrp-fifo-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("mrp.fifo");
+        let status = std::process::Command::new("mkfifo")
+            .arg(&path)
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let target = path.clone();
+        let writer = std::thread::spawn(move || {
+            let mut fifo = std::fs::File::create(target).unwrap();
+            let payload = test_payload();
+            let mut stream = Vec::new();
+            for pdu in [&payload[..], &[0x00], &payload[..]] {
+                stream.extend_from_slice(&(pdu.len() as u16).to_be_bytes());
+                stream.extend_from_slice(pdu);
+            }
+            // A PDU cut short by the writer closing the pipe.
+            stream.extend_from_slice(&[0x00, 0x34, 0x00, 0x01]);
+            fifo.write_all(&stream).unwrap();
+        });
+        let expected = parse_mrp_data(&test_payload());
+        let items: Vec<_> = parse_mrp_frames_from_fifo(&path).collect();
+        writer.join().unwrap();
+        assert_eq!(
+            items,
+            [
+                expected.clone(),
+                Err(MRPParseError::InsufficientData {
+                    needed: 2,
+                    available: 1
+                }),
+                expected,
+                Err(MRPParseError::Io(std::io::ErrorKind::UnexpectedEof)),
+            ]
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        let missing: Vec<_> = parse_mrp_frames_from_fifo(&path).collect();
+        assert_eq!(
+            missing,
+            [Err(MRPParseError::Io(std::io::ErrorKind::NotFound))]
+        );
+        std::fs::remove_dir(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("00 01\n7f0A"), Ok(vec![0x00, 0x01, 0x7f, 0x0a]));
+        assert_eq!(decode_hex(""), Ok(vec![]));
+        assert_eq!(decode_hex("0g"), Err(MRPParseError::InvalidHex));
+        assert_eq!(decode_hex("0 1 2"), Err(MRPParseError::InvalidHex));
+    }
+
+    /// Deterministic xorshift32 driving the generated-input tests below.
+    ///
+    /// These stand in for `proptest`/`Arbitrary`, which are not dependencies so the test suite
+    /// builds without fetching them. The cost is that there is no shrinking: a failure shows
+    /// the input as generated, and the fixed seeds make it reproduce exactly so it can be
+    /// minimized by hand and added to tests/corpus.
+    struct XorShift(u32);
+
+    impl XorShift {
+        fn next(&mut self) -> u32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 17;
+            self.0 ^= self.0 << 5;
+            self.0
+        }
+
+        fn below(&mut self, bound: usize) -> usize {
+            self.next() as usize % bound
+        }
+
+        fn u16(&mut self) -> u16 {
+            self.next() as u16
+        }
+
+        fn bytes<const N: usize>(&mut self) -> [u8; N] {
+            core::array::from_fn(|_| self.next() as u8)
+        }
+
+        fn vec(&mut self, max_len: usize) -> Vec<u8> {
+            (0..self.below(max_len + 1))
+                .map(|_| self.next() as u8)
+                .collect()
+        }
+    }
+
+    /// Feeds `input` to every parsing entry point, as the fuzz target does; any panic fails the
+    /// test. Whatever parses must re-encode to bytes that parse and re-encode identically.
+    fn exercise_all_entry_points(input: &[u8]) {
+        if let Ok(mrp_data) = parse_mrp_data(input) {
+            let _ = (
+                mrp_data.to_string(),
+                mrp_data.summary(),
+                mrp_data.validate(),
+            );
+            let _ = transmit::pre_transmit_check(&mrp_data);
+            let encoded = mrp_data.to_bytes().unwrap();
+            let reparsed = parse_mrp_data(&encoded)
+                .unwrap_or_else(|err| panic!("{:02x?} re-encoded unparseably: {}", input, err));
+            assert_eq!(reparsed.to_bytes().unwrap(), encoded, "{:02x?}", input);
+        }
+        let _ = parse_mrp_data_with_policy(input, UnknownTlvPolicy::Reject);
+        let _ = parse_mrp_data_with_warnings(input);
+        let _ = parse_mrp_tlvs_only(input);
+        let _ = parse_mrp_data_with_remainder(input);
+        let _ = MRPParser::new().strict_version(true).parse(input);
+        if let Ok(mrp_ref) = borrowed::parse_mrp_data_ref(input) {
+            for tlv in &mrp_ref {
+                let _ = tlv.map(|tlv| tlv.data());
+            }
+        }
+        reader::TlvReader::new(input).for_each(drop);
+        let _ = frame::parse_mrp_frame(input);
+        #[cfg(feature = "pcap")]
+        pcap::read_mrp_from_pcap(input).for_each(drop);
+    }
+
+    #[test]
+    fn test_parse_never_panics_on_mutated_payloads() {
+        let payload = test_payload();
+        for len in 0..=payload.len() {
+            exercise_all_entry_points(&payload[..len]);
+        }
+        let mut rng = XorShift(0x2545_f491);
+        for _ in 0..10_000 {
+            let mut mutated = payload.clone();
+            for _ in 0..4 {
+                let index = rng.below(mutated.len());
+                mutated[index] = rng.next() as u8;
+            }
+            exercise_all_entry_points(&mutated);
+        }
+    }
+
+    #[test]
+    fn test_parse_never_panics_on_random_bytes() {
+        let mut rng = XorShift(0x9e37_79b9);
+        for _ in 0..10_000 {
+            let mut input = rng.vec(64);
+            // Mostly start like an MRP PDU so the TLV walk gets exercised, not just the version.
+            if rng.below(4) != 0 && input.len() >= 2 {
+                input[..2].copy_from_slice(&MRP_VERSION.to_be_bytes());
+            }
+            exercise_all_entry_points(&input);
+        }
+    }
+
+    /// Inputs that once broke, or came close to breaking, an entry point.
+    #[test]
+    fn test_corpus_regressions() {
+        let corpus = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/corpus");
+        let mut count = 0;
+        for entry in std::fs::read_dir(corpus).unwrap() {
+            exercise_all_entry_points(&std::fs::read(entry.unwrap().path()).unwrap());
+            count += 1;
+        }
+        assert!(count > 0);
+    }
+
+    fn random_manager_data(rng: &mut XorShift) -> MRPManagerData {
+        MRPManagerData::new(
+            rng.u16(),
+            MacAddress(rng.bytes()),
+            rng.u16(),
+            MacAddress(rng.bytes()),
+        )
+    }
+
+    /// Any sub-TLV other than Ed1.
+    fn random_sub_tlv(rng: &mut XorShift) -> MRPSubTlv {
+        match rng.below(4) {
+            0 => MRPSubTlv::TestMgrNAck(random_manager_data(rng)),
+            1 => MRPSubTlv::TestPropagate(random_manager_data(rng)),
+            2 => MRPSubTlv::AutoMgr,
+            _ => MRPSubTlv::Raw {
+                sub_type: 0x04 + rng.below(0xfc) as u8,
+                value: rng.vec(32),
+            },
+        }
+    }
+
+    /// An MRP_Option that may break the Ed1 rule or be too long, with the error encoding it
+    /// must fail with.
+    fn random_option(rng: &mut XorShift) -> (MRPOptionData, Option<EncodeError>) {
+        let siemens = rng.below(2) == 0;
+        let mut sub_tlvs: Vec<MRPSubTlv> = (0..rng.below(4)).map(|_| random_sub_tlv(rng)).collect();
+        let ed1 = MRPSubTlv::Ed1 {
+            ed1_type: rng.next() as u8,
+            manufacturer_data: rng.u16(),
+        };
+        let mut expected = None;
+        match rng.below(4) {
+            // Ed1 where it belongs.
+            0 => {
+                if siemens {
+                    sub_tlvs.insert(0, ed1);
+                }
+            }
+            // Ed1 after a Siemens option's first entry, or anywhere in another vendor's.
+            1 => {
+                let index = if siemens {
+                    sub_tlvs.insert(0, ed1.clone());
+                    1 + rng.below(sub_tlvs.len())
+                } else {
+                    rng.below(sub_tlvs.len() + 1)
+                };
+                sub_tlvs.insert(index, ed1);
+                expected = Some(EncodeError::MisplacedEd1 { index });
+            }
+            // No Ed1, which a Siemens option needs.
+            2 => {
+                if siemens {
+                    expected = Some(EncodeError::MisplacedEd1 { index: 0 });
+                }
+            }
+            // A Raw value around the 255-byte limit.
+            _ => {
+                if siemens {
+                    sub_tlvs.insert(0, ed1);
+                }
+                sub_tlvs.push(MRPSubTlv::Raw {
+                    sub_type: 0x80,
+                    value: vec![rng.next() as u8; 250 + rng.below(60)],
+                });
+            }
+        }
+        let option = MRPOptionData::new(
+            if siemens {
+                SIEMENS_OUI
+            } else {
+                [0x00, 0x80, 0x63]
+            },
+            sub_tlvs,
+        );
+        let length = option.payload_length();
+        let expected = expected.or((length > 255).then_some(EncodeError::ValueTooLong {
+            tlv_type: 0x7f,
+            length,
+        }));
+        (option, expected)
+    }
+
+    /// Any TLV this crate can encode, other than End.
+    fn random_tlv(rng: &mut XorShift) -> MRPTLVData {
+        let sa = MacAddress(rng.bytes());
+        match rng.below(12) {
+            0 => MRPTLVData::MRPTest(MRPTestData::new(
+                rng.u16(),
+                sa,
+                rng.u16().into(),
+                rng.u16().into(),
+                rng.u16(),
+                rng.next(),
+            )),
+            1 => {
+                MRPTLVData::MRPCommon(MRPCommonData::new(rng.u16(), Uuid::from_bytes(rng.bytes())))
+            }
+            2 => {
+                MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(rng.u16(), sa, rng.u16()))
+            }
+            3 => MRPTLVData::MRPLinkDown(MRPLinkData::new(
+                sa,
+                rng.u16().into(),
+                rng.u16(),
+                rng.u16(),
+            )),
+            4 => {
+                MRPTLVData::MRPLinkUp(MRPLinkData::new(sa, rng.u16().into(), rng.u16(), rng.u16()))
+            }
+            5 => MRPTLVData::MRPInTest(MRPInTestData::new(
+                rng.u16(),
+                sa,
+                rng.u16().into(),
+                rng.u16().into(),
+                rng.u16(),
+                rng.next(),
+            )),
+            6 => MRPTLVData::MRPInTopologyChange(MRPInTopologyChangeData::new(
+                sa,
+                rng.u16(),
+                rng.u16(),
+            )),
+            7 => MRPTLVData::MRPInLinkDown(MRPInLinkData::new(
+                sa,
+                rng.u16().into(),
+                rng.u16(),
+                rng.u16(),
+                rng.u16(),
+            )),
+            8 => MRPTLVData::MRPInLinkUp(MRPInLinkData::new(
+                sa,
+                rng.u16().into(),
+                rng.u16(),
+                rng.u16(),
+                rng.u16(),
+            )),
+            9 => MRPTLVData::MRPInLinkStatusPoll(MRPInLinkStatusPollData::new(
+                sa,
+                rng.u16().into(),
+                rng.u16(),
+            )),
+            10 => {
+                let siemens = rng.below(2) == 0;
+                let mut sub_tlvs = Vec::new();
+                if siemens {
+                    sub_tlvs.push(MRPSubTlv::Ed1 {
+                        ed1_type: rng.next() as u8,
+                        manufacturer_data: rng.u16(),
+                    });
+                }
+                for _ in 0..rng.below(4) {
+                    sub_tlvs.push(random_sub_tlv(rng));
+                }
+                let oui = if siemens {
+                    SIEMENS_OUI
+                } else {
+                    [0x00, 0x80, 0x63]
+                };
+                MRPTLVData::MRPOption(MRPOptionData::new(oui, sub_tlvs))
+            }
+            _ => {
+                // Every type byte the parser does not decode.
+                let tlv_type = match rng.below(0x74 + 0x80) as u8 {
+                    low @ 0..=0x73 => 0x0b + low,
+                    high => high + 0x0c,
+                };
+                MRPTLVData::Unknown {
+                    tlv_type,
+                    raw: rng.vec(64),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_tlv_sequences_round_trip() {
+        let mut rng = XorShift(0x6a09_e667);
+        for _ in 0..2_000 {
+            let mut mrp_data = MRPData::new(rng.u16());
+            for _ in 0..rng.below(8) {
+                // Some options cannot be encoded; push must refuse those and nothing else.
+                let (data, expected) = if rng.below(4) == 0 {
+                    let (option, expected) = random_option(&mut rng);
+                    (MRPTLVData::MRPOption(option), expected)
+                } else {
+                    (random_tlv(&mut rng), None)
+                };
+                assert_eq!(mrp_data.push(data).err(), expected);
+            }
+            if rng.below(4) != 0 {
+                mrp_data.push(MRPTLVData::MRPEnd).unwrap();
+            }
+            let encoded = mrp_data.to_bytes().unwrap();
+            let parsed = parse_mrp_data(&encoded).unwrap();
+            assert_eq!(parsed, mrp_data);
+            assert_eq!(parsed.to_bytes().unwrap(), encoded);
+            assert_eq!(parse_mrp_data(&parsed.to_bytes().unwrap()).unwrap(), parsed);
+        }
+    }
+
+    #[test]
+    fn test_random_options_round_trip_or_fail_to_encode() {
+        let mut rng = XorShift(0xbb67_ae85);
+        for _ in 0..2_000 {
+            let (option, expected) = random_option(&mut rng);
+            let mut mrp_data = MRPData::new(MRP_VERSION);
+            let result = mrp_data.push(MRPTLVData::MRPOption(option.clone()));
+            assert_eq!(result.err(), expected, "{:?}", option);
+            if expected.is_none() {
+                let parsed = parse_mrp_data(&mrp_data.to_bytes().unwrap()).unwrap();
+                assert_eq!(parsed.options().next(), Some(&option));
+            }
+        }
+    }
+
+    #[test]
+    fn test_misplaced_ed1_is_rejected() {
+        let ed1 = MRPSubTlv::Ed1 {
+            ed1_type: 0,
+            manufacturer_data: 0,
+        };
+        for (oui, sub_tlvs, index) in [
+            (SIEMENS_OUI, vec![MRPSubTlv::AutoMgr, ed1.clone()], 0),
+            (SIEMENS_OUI, vec![ed1.clone(), ed1.clone()], 1),
+            ([0x00, 0x80, 0x63], vec![ed1.clone()], 0),
+            ([0x00, 0x80, 0x63], vec![MRPSubTlv::AutoMgr, ed1.clone()], 1),
+        ] {
+            let option = MRPOptionData::new(oui, sub_tlvs);
+            assert_eq!(option.misplaced_ed1(), Some(index));
+            let mut mrp_data = MRPData::new(MRP_VERSION);
+            assert_eq!(
+                mrp_data.push(MRPTLVData::MRPOption(option)),
+                Err(EncodeError::MisplacedEd1 { index })
+            );
+            assert!(mrp_data.tlv_headers.is_empty());
+        }
+        let option = MRPOptionData::new(SIEMENS_OUI, vec![ed1, MRPSubTlv::AutoMgr]);
+        assert_eq!(option.misplaced_ed1(), None);
+        assert_eq!(
+            EncodeError::MisplacedEd1 { index: 1 }.to_string(),
+            "MRP_Option sub-TLV 1 breaks the rule that Ed1 leads a Siemens option only"
+        );
+    }
+
+    #[test]
+    fn test_parse_mrp_data_with_remainder() {
+        let mut input = test_payload();
+        input.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let (mrp_data, remainder) = parse_mrp_data_with_remainder(&input).unwrap();
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+        assert_eq!(remainder, [0xde, 0xad, 0xbe, 0xef]);
+
+        let payload = test_payload();
+        let (_, remainder) = parse_mrp_data_with_remainder(&payload).unwrap();
+        assert!(remainder.is_empty());
+
+        // Without an End TLV, everything is TLVs.
+        let (mrp_data, remainder) = parse_mrp_data_with_remainder(&input[..50]).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 3);
+        assert!(remainder.is_empty());
+        assert_eq!(
+            parse_mrp_data_with_remainder(&input[..51]),
+            Err(MRPParseError::TruncatedHeader { offset: 50 })
+        );
+        assert_eq!(
+            parse_mrp_data_with_remainder(&[0x00]),
+            Err(MRPParseError::InsufficientData {
+                needed: 2,
+                available: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_mrp_data_with_byte_accounting() {
+        let payload = test_payload();
+        let (mrp_data, accounting) = parse_mrp_data_with_byte_accounting(&payload).unwrap();
+        assert_eq!(mrp_data, parse_mrp_data(&payload).unwrap());
+        assert_eq!(
+            accounting,
+            vec![(0x02, 20), (0x01, 20), (0x7f, 8), (0x00, 2)]
+        );
+        let total: usize = accounting.iter().map(|(_, bytes)| bytes).sum();
+        assert_eq!(2 + total, payload.len());
+
+        assert_eq!(
+            parse_mrp_data_with_byte_accounting(&payload[..51]),
+            Err(MRPParseError::TruncatedHeader { offset: 50 })
+        );
+    }
+
+    #[test]
+    fn test_parse_mrp_data_with_remainder_long_trailer() {
+        // The enclosing protocol may carry far more than one PDU's worth after it.
+        let mut input = test_payload();
+        input.resize(MRP_MAX_PDU_SIZE * 2, 0xaa);
+        let (mrp_data, remainder) = parse_mrp_data_with_remainder(&input).unwrap();
+        assert_eq!(mrp_data.tlv_headers.len(), 4);
+        assert_eq!(remainder.len(), MRP_MAX_PDU_SIZE * 2 - 52);
+
+        let mut input = test_payload()[..42].to_vec();
+        while input.len() + 257 <= MRP_MAX_PDU_SIZE {
+            input.extend_from_slice(&[0x80, 0xff]);
+            input.extend_from_slice(&[0; 255]);
+        }
+        input.extend_from_slice(&[0x80, 0xff]);
+        input.extend_from_slice(&[0; 255]);
+        input.extend_from_slice(&[0x00, 0x00]);
+        assert!(matches!(
+            parse_mrp_data_with_remainder(&input),
+            Err(MRPParseError::FrameTooLong { .. })
+        ));
+    }
+
+    #[test]
+    fn test_parse_frame_too_long() {
+        let mut payload = test_payload();
+        payload.resize(MRP_MAX_PDU_SIZE, 0x00);
+        assert!(parse_mrp_data(&payload).is_ok());
+        payload.push(0x00);
+        assert_eq!(
+            parse_mrp_data(&payload),
+            Err(MRPParseError::FrameTooLong {
+                actual: MRP_MAX_PDU_SIZE + 1,
+                max: MRP_MAX_PDU_SIZE
+            })
+        );
+    }
+
+    #[test]
+    fn test_port_role_and_ring_state_values() {
+        assert_eq!(PortRole::from(0x0000), PortRole::Primary);
+        assert_eq!(PortRole::from(0x0001), PortRole::Secondary);
+        assert_eq!(PortRole::from(0x0002), PortRole::Interconnection);
+        assert_eq!(PortRole::from(0x0003), PortRole::Unknown(0x0003));
+        assert_eq!(RingState::from(0x0000), RingState::Open);
+        assert_eq!(RingState::from(0x0001), RingState::Closed);
+        assert_eq!(RingState::from(0xbeef), RingState::Unknown(0xbeef));
+        for value in [0x0000, 0x0001, 0x0002, 0xbeef, 0xffff] {
+            assert_eq!(u16::from(PortRole::from(value)), value);
+            assert_eq!(u16::from(RingState::from(value)), value);
+        }
+    }
+
+    #[test]
+    fn test_manager_priority() {
+        assert!(ManagerPriority(0x8000).is_default());
+        assert!(!ManagerPriority(0xa000).is_default());
+        assert!(ManagerPriority(0xa000).is_auto_manager());
+        assert!(ManagerPriority(0x9000).is_auto_manager());
+        assert!(!ManagerPriority(0x8000).is_auto_manager());
+        assert!(ManagerPriority::HIGHEST < ManagerPriority::DEFAULT);
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let MRPTLVData::MRPTest(data) = &mrp_data.tlv_headers[0].data else {
+            panic!("Expected MRPTest data");
+        };
+        assert_eq!(data.priority(), ManagerPriority::AUTO_MANAGER_DEFAULT);
+    }
+
+    #[test]
+    fn test_tlv_header_try_split() {
+        let payload = test_payload();
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        let splits: Vec<(u8, u8, Vec<u8>)> = mrp_data.iter().map(MRPTLVHeader::try_split).collect();
+        assert_eq!(
+            splits,
+            vec![
+                (0x02, 18, payload[4..22].to_vec()),
+                (0x01, 18, payload[24..42].to_vec()),
+                (0x7f, 6, payload[44..50].to_vec()),
+                (0x00, 0, vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_display_snapshot() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(
+            mrp_data.to_string(),
+            "MRP Version: 0x0001
+  TLV Type: 0x02, Length: 18
+  Data:
+    MRP Test Data:
+      Prio: 0xa000
+      SA: 00:0e:8c:e0:2f:22
+      Port Role: 0x0000 (Primary)
+      Ring State: 0x0000 (Open)
+      Transition: 0x0001
+      Timestamp: 0x19fa3fd4 (435 830 740 ms ≈ 5d 1h)
+  TLV Type: 0x01, Length: 18
+  Data:
+    MRP Common Data:
+      Sequence ID: 0x057e
+      Domain UUID: c3d687fe-789e-03a1-acdb-e5bfcbbc27b6
+  TLV Type: 0x7f, Length: 6
+  Data:
+    MRP Option Data:
+      Manufacturer OUI: 08:00:06
+      Ed1 Type: 0x00
+      Ed1 Manufacturer Data: 0x0000
+  TLV Type: 0x00, Length: 0
+  Data:
+    End of MRP Data
+"
+        );
+    }
+
+    #[test]
+    fn test_summary_snapshot() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        assert_eq!(
+            mrp_data.summary(),
+            "MRP v1 Test sa=00:0e:8c:e0:2f:22 ring=open prio=0xa000 seq=0x057e \
+             uuid=c3d687fe-789e-03a1-acdb-e5bfcbbc27b6"
+        );
+
+        let summary = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap().summary();
+        assert!(
+            summary.starts_with("MRP v1 LinkDown sa=00:1b:1b:6c:52:21 port=secondary interval=20"),
+            "{}",
+            summary
+        );
+        assert_eq!(MRPData::new(1).summary(), "MRP v1 Unknown");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_parse_mrp_data_from_json_value() {
+        let hex: String = test_payload()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect();
+        let value = serde_json::json!({ "hex": hex, "captured_at": 1700000000 });
+        assert_eq!(
+            parse_mrp_data_from_json_value(&value),
+            parse_mrp_data(&test_payload())
+        );
+
+        for malformed in [
+            serde_json::json!({}),
+            serde_json::json!({ "payload": hex }),
+            serde_json::json!({ "hex": 12 }),
+            serde_json::json!({ "hex": null }),
+            serde_json::json!(hex),
+            serde_json::json!([hex]),
+        ] {
+            assert_eq!(
+                parse_mrp_data_from_json_value(&malformed),
+                Err(MRPParseError::MalformedJsonInput),
+                "{}",
+                malformed
+            );
+        }
+        assert_eq!(
+            parse_mrp_data_from_json_value(&serde_json::json!({ "hex": "0g" })),
+            Err(MRPParseError::InvalidHex)
+        );
+        assert_eq!(
+            MRPParseError::MalformedJsonInput.to_string(),
+            "JSON input has no string \"hex\" field"
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_snapshot() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let json = mrp_data.to_json_string().unwrap();
+        assert_eq!(
+            json,
+            concat!(
+                r#"{"version":1,"tlv_headers":["#,
+                r#"{"tlv_type":2,"length":18,"data":{"type":"test","data":{"prio":40960,"#,
+                r#""sa":"00:0e:8c:e0:2f:22","port_role":"Primary","ring_state":"Open","#,
+                r#""transition":1,"timestamp":435830740}}},"#,
+                r#"{"tlv_type":1,"length":18,"data":{"type":"common","data":{"sequence_id":1406,"#,
+                r#""domain_uuid":"c3d687fe-789e-03a1-acdb-e5bfcbbc27b6"}}},"#,
+                r#"{"tlv_type":127,"length":6,"data":{"type":"option","data":{"#,
+                r#""manufacturer_oui":[8,0,6],"sub_tlvs":[{"Ed1":{"ed1_type":0,"manufacturer_data":0}}]}}},"#,
+                r#"{"tlv_type":0,"length":0,"data":{"type":"end"}}]}"#
+            )
+        );
+        assert!(!json.contains('\n'));
+        assert_eq!(serde_json::from_str::<MRPData>(&json).unwrap(), mrp_data);
+    }
+
+    #[test]
+    fn test_display_redacted() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let mut redactor = redact::Redactor::new(3);
+        let plain = mrp_data.to_string();
+        assert!(plain.contains("00:0e:8c:e0:2f:22") && plain.contains("c3d687fe"));
+        let text = mrp_data.display_redacted(&mut redactor).to_string();
+        assert_eq!(
+            text,
+            redact::Redactor::new(3).redacted(&mrp_data).to_string()
+        );
+        assert!(!text.contains("00:0e:8c:e0:2f:22"));
+        assert!(!text.contains("c3d687fe"));
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_to_json_string_redacted() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let mut redactor = redact::Redactor::new(3);
+        let json = mrp_data.to_json_string_redacted(&mut redactor).unwrap();
+        assert!(!json.contains("00:0e:8c:e0:2f:22"));
+        assert!(!json.contains("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6"));
+        assert_eq!(mrp_data, parse_mrp_data(&test_payload()).unwrap());
+
+        let table = redactor.mapping_table();
+        let redacted = serde_json::from_str::<MRPData>(&json).unwrap();
+        assert_eq!(
+            table.restore_uuid(&redacted.domain_uuid().unwrap()),
+            mrp_data.domain_uuid()
+        );
+        assert_eq!(json, redactor.redacted(&mrp_data).to_json_string().unwrap());
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_into_json_value() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let expected: serde_json::Value =
+            serde_json::from_str(&mrp_data.to_json_string().unwrap()).unwrap();
+        let value = mrp_data.into_json_value();
+        assert!(value.is_object());
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_tlv_header_as_slice() {
+        let payload = test_payload();
+        let mrp_data = parse_mrp_data(&payload).unwrap();
+        let slices: Vec<&[u8]> = mrp_data
+            .iter()
+            .map(|header| header.as_slice(&payload).unwrap())
+            .collect();
+        assert_eq!(
+            slices,
+            [
+                &payload[2..22],
+                &payload[22..42],
+                &payload[42..50],
+                &payload[50..52]
+            ]
+        );
+        // Points into the buffer rather than at a copy.
+        assert_eq!(slices[1].as_ptr(), payload[22..].as_ptr());
+
+        let mut other = mrp_data.tlv_headers[1].clone();
+        if let MRPTLVData::MRPCommon(common) = &mut other.data {
+            common.sequence_id += 1;
+        }
+        assert_eq!(other.as_slice(&payload), None);
+        assert_eq!(mrp_data.tlv_headers[0].as_slice(&payload[..10]), None);
+    }
+
+    #[test]
+    fn test_display_symbolic_names() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let text = mrp_data.to_string();
+        assert!(text.contains("      Port Role: 0x0000 (Primary)\n"));
+        assert!(text.contains("      Ring State: 0x0000 (Open)\n"));
+
+        let mut payload = test_payload();
+        payload[13] = 0x07;
+        payload[15] = 0x01;
+        let text = parse_mrp_data(&payload).unwrap().to_string();
+        assert!(text.contains("      Port Role: 0x0007 (Unknown)\n"));
+        assert!(text.contains("      Ring State: 0x0001 (Closed)\n"));
+        assert_eq!(
+            parse_mrp_data(&payload).unwrap().to_bytes().unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_election_key_different_prio() {
+        let low_mac = MacAddress([0x00, 0x0e, 0x8c, 0x00, 0x00, 0x01]);
+        let high_mac = MacAddress([0x00, 0x0e, 0x8c, 0xff, 0xff, 0xff]);
+        let default_manager =
+            MRPTestData::new(0x8000, high_mac, PortRole::Primary, RingState::Closed, 0, 0);
+        let auto_manager =
+            MRPTestData::new(0xa000, low_mac, PortRole::Primary, RingState::Closed, 0, 0);
+        assert!(default_manager.as_election_key() < auto_manager.as_election_key());
+    }
+
+    #[test]
+    fn test_election_key_same_prio() {
+        let low_mac = MacAddress([0x00, 0x0e, 0x8c, 0x00, 0x00, 0x01]);
+        let high_mac = MacAddress([0x00, 0x0e, 0x8c, 0x00, 0x01, 0x00]);
+        let first = MRPTestData::new(0x8000, high_mac, PortRole::Primary, RingState::Open, 0, 0);
+        let second = MRPTestData::new(0x8000, low_mac, PortRole::Primary, RingState::Open, 0, 0);
+        let winner = [first.as_election_key(), second.as_election_key()]
+            .into_iter()
+            .min()
+            .unwrap();
+        assert_eq!(winner, (0x8000, low_mac));
+    }
+
+    #[test]
+    fn test_is_topology_consistent() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let first = MRPTestData::new(0x8000, sa, PortRole::Primary, RingState::Open, 1, 100);
+        let second = MRPTestData::new(0x8000, sa, PortRole::Secondary, RingState::Closed, 2, 120);
+        assert!(first.is_topology_consistent(&second));
+
+        let other_sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x23]);
+        let moved = MRPTestData::new(0x8000, other_sa, PortRole::Primary, RingState::Open, 1, 100);
+        let reprioritised =
+            MRPTestData::new(0xa000, sa, PortRole::Primary, RingState::Open, 1, 100);
+        assert!(!first.is_topology_consistent(&moved));
+        assert!(!first.is_topology_consistent(&reprioritised));
+    }
+
+    fn assert_copy<T: Copy + Eq + core::hash::Hash + Default>() {}
+    fn assert_clone<T: Clone + Eq + core::hash::Hash>() {}
+
+    #[test]
+    fn test_trait_guarantees() {
+        assert_copy::<MacAddress>();
+        assert_copy::<PortRole>();
+        assert_copy::<RingState>();
+        assert_copy::<MRPTestData>();
+        assert_copy::<MRPCommonData>();
+        assert_copy::<MRPTopologyChangeData>();
+        assert_copy::<MRPLinkData>();
+        assert_copy::<MRPInTestData>();
+        assert_copy::<MRPInTopologyChangeData>();
+        assert_copy::<MRPInLinkData>();
+        assert_copy::<MRPInLinkStatusPollData>();
+        assert_copy::<MRPManagerData>();
+        assert_clone::<MRPData>();
+        assert_clone::<MRPTLVHeader>();
+        assert_clone::<MRPTLVData>();
+        assert_clone::<MRPOptionData>();
+        assert_clone::<MRPSubTlv>();
+
+        assert_eq!(MacAddress::default().octets(), [0; 6]);
+        // Defaults are the all-zero wire encoding.
+        let mut buf = Vec::new();
+        MRPTestData::default().write_bytes(&mut buf);
+        assert_eq!(buf, [0; 18]);
+    }
+
+    #[test]
+    fn test_parsed_frames_in_hash_collections() {
+        use std::collections::{HashMap, HashSet};
+
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let sa = mrp_data.test().unwrap().sa;
+        let mut by_sa = HashMap::new();
+        by_sa.insert(sa, mrp_data.clone());
+        assert_eq!(by_sa[&sa], mrp_data);
+
+        let mut seen = HashSet::new();
+        assert!(seen.insert(mrp_data.clone()));
+        assert!(!seen.insert(parse_mrp_data(&test_payload()).unwrap()));
+        let mut other = mrp_data;
+        other.tlv_headers[1].data = MRPTLVData::MRPCommon(MRPCommonData::new(1, Uuid::nil()));
+        assert!(seen.insert(other));
+    }
+
+    #[test]
+    fn test_eq_ignoring_timestamp() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let mut payload = test_payload();
+        payload[18..22].copy_from_slice(&0x19fa_4000u32.to_be_bytes());
+        let later = parse_mrp_data(&payload).unwrap();
+        assert_ne!(later, mrp_data);
+        assert!(later.eq_ignoring_timestamp(&mrp_data));
+        assert!(mrp_data.eq_ignoring_timestamp(&mrp_data));
+
+        payload[15] = 0x01;
+        let closed = parse_mrp_data(&payload).unwrap();
+        assert!(!closed.eq_ignoring_timestamp(&mrp_data));
+
+        let mut without_end = mrp_data.clone();
+        without_end.tlv_headers.pop();
+        assert!(!without_end.eq_ignoring_timestamp(&mrp_data));
+    }
+
+    #[test]
+    fn test_eq_ignoring_sequence_id() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let mut payload = test_payload();
+        payload[24..26].copy_from_slice(&0x057fu16.to_be_bytes());
+        let next = parse_mrp_data(&payload).unwrap();
+        assert_ne!(next, mrp_data);
+        assert!(next.eq_ignoring_sequence_id(&mrp_data));
+        assert!(!next.eq_ignoring_timestamp(&mrp_data));
+
+        payload[26] ^= 0xff;
+        let other_domain = parse_mrp_data(&payload).unwrap();
+        assert!(!other_domain.eq_ignoring_sequence_id(&mrp_data));
+
+        let mut payload = test_payload();
+        payload[18..22].copy_from_slice(&0u32.to_be_bytes());
+        let restamped = parse_mrp_data(&payload).unwrap();
+        assert!(!restamped.eq_ignoring_sequence_id(&mrp_data));
+    }
+
+    #[test]
+    fn test_is_equal_type() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        // Two differently-valued instances of every variant, in the same order.
+        let variants = |n: u16| {
+            let n8 = n as u8;
+            vec![
+                MRPTLVData::MRPTest(MRPTestData::new(
+                    n,
+                    sa,
+                    PortRole::Primary,
+                    RingState::Open,
+                    n,
+                    n.into(),
+                )),
+                MRPTLVData::MRPCommon(MRPCommonData::new(n, Uuid::from_u128(n.into()))),
+                MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(n, sa, n)),
+                MRPTLVData::MRPLinkDown(MRPLinkData::new(sa, PortRole::Primary, n, 0)),
+                MRPTLVData::MRPLinkUp(MRPLinkData::new(sa, PortRole::Secondary, n, 1)),
+                MRPTLVData::MRPInTest(MRPInTestData::new(
+                    n,
+                    sa,
+                    PortRole::Interconnection,
+                    RingState::Closed,
+                    0,
+                    0,
+                )),
+                MRPTLVData::MRPInTopologyChange(MRPInTopologyChangeData::new(sa, n, 0)),
+                MRPTLVData::MRPInLinkDown(MRPInLinkData::new(
+                    sa,
+                    PortRole::Interconnection,
+                    n,
+                    0,
+                    0,
+                )),
+                MRPTLVData::MRPInLinkUp(MRPInLinkData::new(sa, PortRole::Interconnection, n, 0, 0)),
+                MRPTLVData::MRPInLinkStatusPoll(MRPInLinkStatusPollData::new(
+                    sa,
+                    PortRole::Interconnection,
+                    n,
+                )),
+                MRPTLVData::MRPOption(MRPOptionData::new([n8, 0x80, 0x63], vec![])),
+                MRPTLVData::MRPEnd,
+                MRPTLVData::Unknown {
+                    tlv_type: 0x80 + n8,
+                    raw: vec![n8],
+                },
+            ]
+        };
+        let left = variants(1);
+        let right = variants(2);
+        for (i, a) in left.iter().enumerate() {
+            for (j, b) in right.iter().enumerate() {
+                assert_eq!(a.is_equal_type(b), i == j, "{:?} vs {:?}", a, b);
+                assert_eq!(b.is_equal_type(a), i == j);
+            }
+            assert!(a.is_equal_type(a));
+            if a != &MRPTLVData::MRPEnd {
+                assert_ne!(a, &right[i]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_timestamp_durations() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let test = |timestamp| {
+            MRPTestData::new(
+                0x8000,
+                sa,
+                PortRole::Primary,
+                RingState::Closed,
+                0,
+                timestamp,
+            )
+        };
+        let reference = test(0x19fa3fd4);
+        assert_eq!(reference.timestamp_ms(), 435_830_740);
+        assert_eq!(
+            reference.timestamp_duration(),
+            Duration::from_millis(435_830_740)
+        );
+        assert_eq!(
+            test(20).age_since(&test(0)),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(test(0).age_since(&test(20)), None);
+        assert_eq!(test(7).age_since(&test(7)), Some(Duration::ZERO));
+
+        // The counter rolls over between the two frames.
+        assert_eq!(
+            test(0x0000_000a).age_since(&test(0xffff_fff6)),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(
+            test(0x0000_0000).age_since(&test(0xffff_ffff)),
+            Some(Duration::from_millis(1))
+        );
+        assert_eq!(test(0xffff_ffff).age_since(&test(0x0000_0000)), None);
+        assert_eq!(
+            test(0x7fff_ffff).age_since(&test(0)),
+            Some(Duration::from_millis(0x7fff_ffff))
+        );
+        assert_eq!(test(0x8000_0000).age_since(&test(0)), None);
+
+        let in_test = |timestamp| {
+            MRPInTestData::new(
+                1,
+                sa,
+                PortRole::Interconnection,
+                RingState::Open,
+                0,
+                timestamp,
+            )
+        };
+        assert_eq!(
+            in_test(0x0000_0004).age_since(&in_test(0xffff_fffc)),
+            Some(Duration::from_millis(8))
+        );
+        assert_eq!(
+            in_test(0xffff_ffff).timestamp_duration(),
+            Duration::from_millis(0xffff_ffff)
+        );
+    }
+
+    #[test]
+    fn test_interval_durations() {
+        let sa = MacAddress([0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21]);
+        let twenty = Duration::from_millis(20);
+        assert_eq!(
+            MRPTopologyChangeData::new(0x8000, sa, 20).interval_duration(),
+            twenty
+        );
+        assert_eq!(
+            MRPLinkData::new(sa, PortRole::Primary, 20, 0).interval_duration(),
+            twenty
+        );
+        assert_eq!(
+            MRPInTopologyChangeData::new(sa, 1, 20).interval_duration(),
+            twenty
+        );
+        let in_link = MRPInLinkData::new(sa, PortRole::Interconnection, 1, 0xffff, 0);
+        assert_eq!(in_link.interval_ms(), 0xffff);
+        assert_eq!(in_link.interval_duration(), Duration::from_millis(65_535));
+    }
+
+    #[test]
+    fn test_millis_display() {
+        assert_eq!(Millis(0).to_string(), "0 ms");
+        assert_eq!(Millis(20).to_string(), "20 ms");
+        assert_eq!(Millis(999).to_string(), "999 ms");
+        assert_eq!(Millis(1500).to_string(), "1 500 ms ≈ 1s");
+        assert_eq!(Millis(65_535).to_string(), "65 535 ms ≈ 1m 5s");
+        assert_eq!(Millis(3_600_000).to_string(), "3 600 000 ms ≈ 1h");
+        assert_eq!(Millis(435_830_740).to_string(), "435 830 740 ms ≈ 5d 1h");
+        assert_eq!(
+            Millis(0xffff_ffff).to_string(),
+            "4 294 967 295 ms ≈ 49d 17h"
+        );
+    }
+
+    #[test]
+    fn test_interpolate_timestamp() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let test = |timestamp| {
+            MRPTestData::new(
+                0x8000,
+                sa,
+                PortRole::Primary,
+                RingState::Closed,
+                0,
+                timestamp,
+            )
+        };
+        let (before, after) = (test(1000), test(1040));
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 0.0),
+            1000
+        );
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 0.5),
+            1020
+        );
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 1.0),
+            1040
+        );
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 0.25),
+            1010
+        );
+
+        // Out-of-range fractions are clamped.
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, -1.0),
+            1000
+        );
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 2.0),
+            1040
+        );
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, f64::NAN),
+            1000
+        );
+
+        // The counter wraps between the two frames.
+        let (before, after) = (test(0xffff_fff0), test(0x0000_0010));
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 0.0),
+            0xffff_fff0
+        );
+        assert_eq!(MRPTestData::interpolate_timestamp(&before, &after, 0.5), 0);
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 1.0),
+            0x0000_0010
+        );
+
+        let (before, after) = (test(0), test(u32::MAX));
+        assert_eq!(
+            MRPTestData::interpolate_timestamp(&before, &after, 1.0),
+            u32::MAX
+        );
+    }
+
+    #[test]
+    fn test_common_add_sequence() {
+        let domain = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        let common = MRPCommonData::new(0x057e, domain);
+        let next = &common + 1;
+        assert_eq!(next, MRPCommonData::new(0x057f, domain));
+        assert_eq!(&common + 0, common);
+        assert_eq!((&common + u16::MAX).sequence_id, 0x057d);
+        assert_eq!(
+            &MRPCommonData::new(u16::MAX, domain) + 1,
+            MRPCommonData::new(0, domain)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_common_data_decode_from_reader() {
+        let payload = test_payload();
+        let mut cursor = std::io::Cursor::new(&payload[24..]);
+        let common = MRPCommonData::decode_from_reader(&mut cursor).unwrap();
+        assert_eq!(Some(&common), parse_mrp_data(&payload).unwrap().common());
+        assert_eq!(cursor.position(), 18);
+
+        let mut truncated = std::io::Read::take(&payload[24..], 10);
+        assert_eq!(
+            MRPCommonData::decode_from_reader(&mut truncated),
+            Err(MRPParseError::Io(std::io::ErrorKind::UnexpectedEof))
+        );
+    }
+
+    #[test]
+    fn test_apply_replay_offset() {
+        let capture = parse_mrp_data(&test_payload()).unwrap();
+        let mut frames: Vec<MRPData> = [0x057e, 0x057f, 0x0582]
+            .into_iter()
+            .map(|sequence_id| {
+                let mut mrp_data = capture.clone();
+                mrp_data.replace_common_tlv(MRPCommonData::new(
+                    sequence_id,
+                    capture.domain_uuid().unwrap(),
+                ));
+                mrp_data
+            })
+            .collect();
+        frames.insert(0, MRPData::with_version_1());
+
+        MRPData::apply_replay_offset(&mut frames, 0xfffe);
+        let sequence_ids: Vec<_> = frames.iter().map(MRPData::sequence_id).collect();
+        assert_eq!(
+            sequence_ids,
+            [None, Some(0xfffe), Some(0xffff), Some(0x0002)]
+        );
+        assert_eq!(frames[1].domain_uuid(), capture.domain_uuid());
+        assert!(frames[1].eq_ignoring_sequence_id(&capture));
+
+        MRPData::apply_replay_offset(&mut frames[2..], 100);
+        assert_eq!(frames[2].sequence_id(), Some(100));
+        assert_eq!(frames[3].sequence_id(), Some(103));
+        MRPData::apply_replay_offset(&mut [], 1);
+    }
+
+    #[test]
+    fn test_common_next_sequence_id() {
+        let domain = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        let common = MRPCommonData::new(0xfffe, domain);
+        let next = common.next_sequence_id();
+        assert_eq!(next, MRPCommonData::new(0xffff, domain));
+        assert_eq!(next.next_sequence_id(), MRPCommonData::new(0x0000, domain));
+        assert_eq!(common.sequence_id, 0xfffe);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_test_data_decode_from_reader() {
+        let payload = test_payload();
+        let mut cursor = std::io::Cursor::new(&payload[4..]);
+        let test = MRPTestData::decode_from_reader(&mut cursor).unwrap();
+        assert_eq!(Some(&test), parse_mrp_data(&payload).unwrap().test());
+        assert_eq!(cursor.position(), 18);
+
+        let mut short = std::io::Cursor::new(&payload[4..20]);
+        assert_eq!(
+            MRPTestData::decode_from_reader(&mut short),
+            Err(MRPParseError::Io(std::io::ErrorKind::UnexpectedEof))
+        );
+    }
+
+    #[test]
+    fn test_is_backup_manager() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let test = |port_role, transition| {
+            MRPTestData::new(0xa000, sa, port_role, RingState::Closed, transition, 0)
+        };
+        assert!(test(PortRole::Secondary, 1).is_backup_manager());
+        assert!(test(PortRole::Secondary, 0xffff).is_backup_manager());
+        assert!(!test(PortRole::Secondary, 0).is_backup_manager());
+        assert!(!test(PortRole::Primary, 3).is_backup_manager());
+        assert!(!test(PortRole::Interconnection, 3).is_backup_manager());
+    }
+
+    #[test]
+    fn test_test_data_numeric_fields() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        for (ring_state, expected) in [
+            (RingState::Open, 0x0000),
+            (RingState::Closed, 0x0001),
+            (RingState::Unknown(0x1234), 0x1234),
+        ] {
+            let test = MRPTestData::new(0xa000, sa, PortRole::Primary, ring_state, 0, 0);
+            assert_eq!(test.ring_state_numeric(), expected);
+        }
+        for (port_role, expected) in [
+            (PortRole::Primary, 0x0000),
+            (PortRole::Secondary, 0x0001),
+            (PortRole::Interconnection, 0x0002),
+            (PortRole::Unknown(0x0007), 0x0007),
+        ] {
+            let test = MRPTestData::new(0xa000, sa, port_role, RingState::Open, 0, 0);
+            assert_eq!(test.port_role_numeric(), expected);
+        }
+    }
+
+    #[test]
+    fn test_simulate_ring_change() {
+        let start = *parse_mrp_data(&test_payload()).unwrap().test().unwrap();
+        let closed = start.simulate_ring_change(RingState::Closed);
+        let opened = closed.simulate_ring_change(RingState::Open);
+        let reclosed = opened.simulate_ring_change(RingState::Closed);
+        assert_eq!(
+            [closed.transition, opened.transition, reclosed.transition],
+            [2, 3, 4]
+        );
+        assert_eq!(reclosed.transition_delta_from(&start), 3);
+        assert_eq!(reclosed.ring_state, RingState::Closed);
+        assert_eq!(reclosed.timestamp, start.timestamp + 30);
+        assert_eq!(reclosed.age_since(&start), Some(Duration::from_millis(30)));
+        assert_eq!((reclosed.prio, reclosed.sa), (start.prio, start.sa));
+        assert!(MRPTestData::verify_ring_state_consistency(&start, &closed));
+
+        let wrapped = MRPTestData {
+            transition: u16::MAX,
+            timestamp: u32::MAX - 5,
+            ..start
+        }
+        .simulate_ring_change(RingState::Open);
+        assert_eq!((wrapped.transition, wrapped.timestamp), (0, 4));
+    }
+
+    #[test]
+    fn test_transition_delta_from() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let test = |transition| {
+            MRPTestData::new(
+                0x8000,
+                sa,
+                PortRole::Primary,
+                RingState::Open,
+                transition,
+                0,
+            )
+        };
+        assert_eq!(test(5).transition_delta_from(&test(2)), 3);
+        assert_eq!(test(2).transition_delta_from(&test(2)), 0);
+        assert_eq!(test(0x0001).transition_delta_from(&test(0xfffe)), 3);
+        assert_eq!(test(0x0000).transition_delta_from(&test(0xffff)), 1);
+        assert_eq!(test(2).transition_delta_from(&test(5)), 0xfffd);
+    }
+
+    #[test]
+    fn test_verify_ring_state_consistency() {
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        let test = |ring_state, transition| {
+            MRPTestData::new(0x8000, sa, PortRole::Primary, ring_state, transition, 0)
+        };
+        let prev = test(RingState::Closed, 3);
+
+        // Changed and counted, changed but not counted.
+        assert!(MRPTestData::verify_ring_state_consistency(
+            &prev,
+            &test(RingState::Open, 4)
+        ));
+        assert!(!MRPTestData::verify_ring_state_consistency(
+            &prev,
+            &test(RingState::Open, 3)
+        ));
+        assert!(!MRPTestData::verify_ring_state_consistency(
+            &prev,
+            &test(RingState::Open, 2)
+        ));
+
+        // Unchanged and not counted, unchanged but counted.
+        assert!(MRPTestData::verify_ring_state_consistency(
+            &prev,
+            &test(RingState::Closed, 3)
+        ));
+        assert!(!MRPTestData::verify_ring_state_consistency(
+            &prev,
+            &test(RingState::Closed, 4)
+        ));
+
+        assert!(MRPTestData::verify_ring_state_consistency(
+            &test(RingState::Open, 0xffff),
+            &test(RingState::Closed, 0x0000)
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_json_round_trip() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let json = serde_json::to_string(&mrp_data).unwrap();
+        assert!(json.contains(r#""sa":"00:0e:8c:e0:2f:22""#));
+        assert!(json.contains(r#""type":"test","data":{"#));
+        assert!(json.contains(r#""domain_uuid":"c3d687fe-789e-03a1-acdb-e5bfcbbc27b6""#));
+        assert!(json.contains(r#"{"type":"end"}"#));
+        let decoded: MRPData = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, mrp_data);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_bincode_round_trip() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let bytes = bincode::serialize(&mrp_data).unwrap();
+        let decoded: MRPData = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded, mrp_data);
+
+        let sa = MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]);
+        assert_eq!(bincode::serialize(&sa).unwrap(), sa.0);
+    }
+
+    #[cfg(feature = "msgpack")]
+    #[test]
+    fn test_msgpack_round_trip() {
+        let mrp_data = parse_mrp_data(&test_payload()).unwrap();
+        let bytes = mrp_data.to_msgpack().unwrap();
+        assert_eq!(MRPData::from_msgpack(&bytes).unwrap(), mrp_data);
+        assert!(MRPData::from_msgpack(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_bad_mac() {
+        assert!(serde_json::from_str::<MacAddress>(r#""00:0e:8c:e0:2f""#).is_err());
+        assert!(serde_json::from_str::<MacAddress>(r#""00:0e:8c:e0:2f:22:11""#).is_err());
+        assert!(serde_json::from_str::<MacAddress>(r#""00:0e:8c:e0:2f:zz""#).is_err());
+    }
 }
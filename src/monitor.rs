@@ -0,0 +1,686 @@
+use crate::{MRPCommonData, MRPData, RingState};
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::time::Duration;
+use uuid::Uuid;
+
+/// Something a [`RingMonitor`] noticed about an MRP domain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RingEvent {
+    /// The MRP_Common sequence ID skipped ahead of the previous one for this domain.
+    SequenceGap {
+        domain_uuid: Uuid,
+        expected: u16,
+        got: u16,
+    },
+    /// A Test frame reported a different ring state than the previous one.
+    RingStateChanged {
+        domain_uuid: Uuid,
+        from: RingState,
+        to: RingState,
+    },
+    /// No Test frame has arrived for the configured interval since `last_test`.
+    TestTimeout {
+        domain_uuid: Uuid,
+        last_test: Duration,
+    },
+}
+
+/// What a [`RingMonitor`] last saw for one domain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DomainStatus {
+    /// Highest sequence ID seen, allowing for wrap; duplicates and late PDUs leave it alone.
+    pub last_sequence_id: Option<u16>,
+    /// The `timestamp` field of the last MRP_Test TLV.
+    pub last_test_timestamp: Option<u32>,
+    pub ring_state: Option<RingState>,
+    /// Caller-supplied receive time of the last Test frame.
+    pub last_test_received: Option<Duration>,
+    /// Whether a [`RingEvent::TestTimeout`] has been reported since the last Test frame.
+    pub test_timed_out: bool,
+    /// Receive time of the Test frame that changed to `ring_state`; `None` until a change is seen.
+    pub ring_state_since: Option<Duration>,
+    /// Receive times of the most recent ring-state changes, oldest first, at most 256 of them.
+    pub state_changes: VecDeque<Duration>,
+}
+
+/// Running total of completed ring-state periods.
+#[derive(Debug, Clone, Copy, Default)]
+struct Periods {
+    total: Duration,
+    count: u32,
+}
+
+impl Periods {
+    fn record(&mut self, period: Duration) {
+        self.total += period;
+        self.count += 1;
+    }
+
+    fn average(&self) -> Option<Duration> {
+        (self.count > 0).then(|| self.total / self.count)
+    }
+}
+
+/// How many ring-state changes [`RingMonitor::flap_count`] remembers per domain.
+const STATE_CHANGE_HISTORY: usize = 256;
+
+/// Tracks sequence IDs, ring state and Test frame arrival per domain UUID.
+///
+/// The monitor never reads a clock: receive times are whatever monotonic time the caller
+/// passes to [`observe`](RingMonitor::observe) and [`poll`](RingMonitor::poll).
+#[derive(Debug, Clone)]
+pub struct RingMonitor {
+    test_timeout: Duration,
+    domains: BTreeMap<Uuid, DomainStatus>,
+    sequences: SequenceTracker,
+    open_periods: Periods,
+    closed_periods: Periods,
+    /// Latest time passed to `observe` or `poll`.
+    latest: Duration,
+}
+
+impl RingMonitor {
+    /// `test_timeout` is how long a domain may go without a Test frame before
+    /// [`poll`](RingMonitor::poll) reports it.
+    pub fn new(test_timeout: Duration) -> Self {
+        RingMonitor {
+            test_timeout,
+            domains: BTreeMap::new(),
+            sequences: SequenceTracker::new(),
+            open_periods: Periods::default(),
+            closed_periods: Periods::default(),
+            latest: Duration::ZERO,
+        }
+    }
+
+    pub fn status(&self, domain_uuid: &Uuid) -> Option<&DomainStatus> {
+        self.domains.get(domain_uuid)
+    }
+
+    /// Records a received PDU. PDUs without an MRP_Common TLV carry no domain and are ignored.
+    ///
+    /// Sequence IDs are judged as by [`SequenceTracker`]: a duplicate or late PDU is
+    /// dropped without events, so it cannot roll the ring state back.
+    pub fn observe(&mut self, mrp_data: &MRPData, received_at: Duration) -> Vec<RingEvent> {
+        let mut events = Vec::new();
+        self.latest = self.latest.max(received_at);
+        let Some(common) = mrp_data.common() else {
+            return events;
+        };
+        let domain_uuid = common.domain_uuid;
+        let mut gaps = Vec::new();
+        if !self.sequences.advance(common, &mut gaps) {
+            return events;
+        }
+        events.extend(gaps.into_iter().map(|gap| RingEvent::SequenceGap {
+            domain_uuid,
+            expected: gap.expected,
+            got: gap.got,
+        }));
+        let status = self.domains.entry(domain_uuid).or_default();
+        status.last_sequence_id = Some(common.sequence_id);
+
+        if let Some(test) = mrp_data.test() {
+            if let Some(from) = status.ring_state.filter(|from| *from != test.ring_state) {
+                events.push(RingEvent::RingStateChanged {
+                    domain_uuid,
+                    from,
+                    to: test.ring_state,
+                });
+                if let Some(since) = status.ring_state_since {
+                    let period = received_at.saturating_sub(since);
+                    match from {
+                        RingState::Open => self.open_periods.record(period),
+                        RingState::Closed => self.closed_periods.record(period),
+                        RingState::Unknown(_) => {}
+                    }
+                }
+                status.ring_state_since = Some(received_at);
+                if status.state_changes.len() == STATE_CHANGE_HISTORY {
+                    status.state_changes.pop_front();
+                }
+                status.state_changes.push_back(received_at);
+            }
+            status.ring_state = Some(test.ring_state);
+            status.last_test_timestamp = Some(test.timestamp);
+            status.last_test_received = Some(received_at);
+            status.test_timed_out = false;
+        }
+        events
+    }
+
+    /// Mean time the ring stayed open, over every domain, between two observed state changes.
+    ///
+    /// The period in progress and the one before the first change, whose start was not seen,
+    /// are not included. `None` until an open period has completed.
+    pub fn average_ring_open_duration(&self) -> Option<Duration> {
+        self.open_periods.average()
+    }
+
+    /// Like [`average_ring_open_duration`](RingMonitor::average_ring_open_duration), for closed
+    /// periods.
+    pub fn average_ring_closed_duration(&self) -> Option<Duration> {
+        self.closed_periods.average()
+    }
+
+    /// Counts ring-state changes of one domain received at most `window` before the latest
+    /// time passed to [`observe`](RingMonitor::observe) or [`poll`](RingMonitor::poll).
+    ///
+    /// Only the last 256 changes of each domain are remembered, so the count never exceeds
+    /// that. 0 for a domain that has not been seen.
+    pub fn flap_count(&self, domain_uuid: &Uuid, window: Duration) -> usize {
+        self.domains.get(domain_uuid).map_or(0, |status| {
+            status
+                .state_changes
+                .iter()
+                .rev()
+                .take_while(|at| self.latest.saturating_sub(**at) <= window)
+                .count()
+        })
+    }
+
+    /// Whether at least `threshold` ring-state changes of the domain fall within the trailing
+    /// `window`.
+    pub fn is_flapping(&self, domain_uuid: &Uuid, window: Duration, threshold: usize) -> bool {
+        self.flap_count(domain_uuid, window) >= threshold
+    }
+
+    /// Reports every domain whose last Test frame is at least the timeout older than `now`.
+    ///
+    /// Each silence is reported once; the next Test frame for the domain re-arms it.
+    pub fn poll(&mut self, now: Duration) -> Vec<RingEvent> {
+        let mut events = Vec::new();
+        self.latest = self.latest.max(now);
+        for (domain_uuid, status) in &mut self.domains {
+            let Some(last_test) = status.last_test_received else {
+                continue;
+            };
+            if !status.test_timed_out && now.saturating_sub(last_test) >= self.test_timeout {
+                status.test_timed_out = true;
+                events.push(RingEvent::TestTimeout {
+                    domain_uuid: *domain_uuid,
+                    last_test,
+                });
+            }
+        }
+        events
+    }
+}
+
+/// Sequence IDs skipped between two MRP_Common TLVs of one domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub domain_uuid: Uuid,
+    /// The sequence ID that should have come next.
+    pub expected: u16,
+    pub got: u16,
+}
+
+impl SequenceGap {
+    /// How many PDUs were lost, accounting for the sequence ID wrapping past 0xffff.
+    pub fn missing(&self) -> u16 {
+        self.got.wrapping_sub(self.expected)
+    }
+}
+
+/// Counts lost PDUs from the MRP_Common sequence IDs of each domain.
+///
+/// A sequence ID up to 0x7fff ahead of the last one is a step forward, so the counter may
+/// wrap; anything else is a duplicate or a late arrival. Those are ignored, since each ID
+/// was already counted once, as observed or as lost, so observed plus lost stays the number
+/// of PDUs sent.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceTracker {
+    last_sequence_ids: BTreeMap<Uuid, u16>,
+    observed: u64,
+    lost: u64,
+}
+
+impl SequenceTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one Common TLV, returning the gap since the previous one of its domain.
+    pub fn observe(&mut self, common: &MRPCommonData) -> Vec<SequenceGap> {
+        let mut gaps = Vec::new();
+        self.advance(common, &mut gaps);
+        gaps
+    }
+
+    /// Like [`observe`](Self::observe), but also says whether the PDU was counted, i.e. was
+    /// neither a duplicate nor a late arrival.
+    fn advance(&mut self, common: &MRPCommonData, gaps: &mut Vec<SequenceGap>) -> bool {
+        if let Some(&last) = self.last_sequence_ids.get(&common.domain_uuid) {
+            let step = common.sequence_id.wrapping_sub(last);
+            if step == 0 || step >= 0x8000 {
+                return false;
+            }
+            if step > 1 {
+                let gap = SequenceGap {
+                    domain_uuid: common.domain_uuid,
+                    expected: last.wrapping_add(1),
+                    got: common.sequence_id,
+                };
+                self.lost += u64::from(gap.missing());
+                gaps.push(gap);
+            }
+        }
+        self.observed += 1;
+        self.last_sequence_ids
+            .insert(common.domain_uuid, common.sequence_id);
+        true
+    }
+
+    /// Lost PDUs as a fraction of those sent, i.e. observed plus lost; 0 before any PDU.
+    pub fn loss_rate(&self) -> f64 {
+        let sent = self.observed + self.lost;
+        if sent == 0 {
+            0.0
+        } else {
+            self.lost as f64 / sent as f64
+        }
+    }
+
+    /// PDUs counted so far, excluding duplicates and late arrivals.
+    pub fn total_observed(&self) -> u64 {
+        self.observed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        MRPCommonData, MRPTLVData, MRPTestData, MRPTopologyChangeData, MacAddress, PortRole,
+    };
+
+    const DOMAIN_A: Uuid = Uuid::from_u128(0xc3d687fe_789e_03a1_acdb_e5bfcbbc27b6);
+    const DOMAIN_B: Uuid = Uuid::from_u128(0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff);
+
+    fn sa() -> MacAddress {
+        MacAddress::from([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22])
+    }
+
+    fn test_frame(domain_uuid: Uuid, sequence_id: u16, ring_state: RingState) -> MRPData {
+        let mut mrp_data = MRPData::new(1);
+        mrp_data
+            .push(MRPTLVData::MRPTest(MRPTestData::new(
+                0xa000,
+                sa(),
+                PortRole::Primary,
+                ring_state,
+                0,
+                u32::from(sequence_id) * 20,
+            )))
+            .unwrap();
+        mrp_data
+            .push(MRPTLVData::MRPCommon(MRPCommonData::new(
+                sequence_id,
+                domain_uuid,
+            )))
+            .unwrap();
+        mrp_data.push(MRPTLVData::MRPEnd).unwrap();
+        mrp_data
+    }
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn test_interleaved_ordered_sequences() {
+        let mut monitor = RingMonitor::new(ms(100));
+        for (n, sequence_id) in (0..10).enumerate() {
+            let at = ms(n as u64 * 20);
+            let a = test_frame(DOMAIN_A, sequence_id, RingState::Closed);
+            let b = test_frame(DOMAIN_B, 1000 + sequence_id, RingState::Closed);
+            assert_eq!(monitor.observe(&a, at), vec![]);
+            assert_eq!(monitor.observe(&b, at), vec![]);
+        }
+        let a = monitor.status(&DOMAIN_A).unwrap();
+        assert_eq!(a.last_sequence_id, Some(9));
+        assert_eq!(a.last_test_timestamp, Some(180));
+        assert_eq!(a.ring_state, Some(RingState::Closed));
+        assert_eq!(
+            monitor.status(&DOMAIN_B).unwrap().last_sequence_id,
+            Some(1009)
+        );
+    }
+
+    #[test]
+    fn test_sequence_gap_is_per_domain() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 1, RingState::Closed), ms(0));
+        monitor.observe(&test_frame(DOMAIN_B, 7, RingState::Closed), ms(0));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 4, RingState::Closed), ms(20)),
+            vec![RingEvent::SequenceGap {
+                domain_uuid: DOMAIN_A,
+                expected: 2,
+                got: 4,
+            }]
+        );
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_B, 8, RingState::Closed), ms(20)),
+            vec![]
+        );
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 5, RingState::Closed), ms(40)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_is_not_a_gap() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 1, RingState::Closed), ms(0));
+        monitor.observe(&test_frame(DOMAIN_A, 2, RingState::Closed), ms(20));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 2, RingState::Open), ms(21)),
+            vec![]
+        );
+        let status = monitor.status(&DOMAIN_A).unwrap();
+        assert_eq!(status.last_sequence_id, Some(2));
+        assert_eq!(status.ring_state, Some(RingState::Closed));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 3, RingState::Closed), ms(40)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_reordered_frame_is_not_a_gap() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 1, RingState::Closed), ms(0));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 3, RingState::Open), ms(20)),
+            vec![
+                RingEvent::SequenceGap {
+                    domain_uuid: DOMAIN_A,
+                    expected: 2,
+                    got: 3,
+                },
+                RingEvent::RingStateChanged {
+                    domain_uuid: DOMAIN_A,
+                    from: RingState::Closed,
+                    to: RingState::Open,
+                }
+            ]
+        );
+        // Frame 2 arrives late: it neither reports a gap nor moves the state back.
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 2, RingState::Closed), ms(21)),
+            vec![]
+        );
+        let status = monitor.status(&DOMAIN_A).unwrap();
+        assert_eq!(status.last_sequence_id, Some(3));
+        assert_eq!(status.ring_state, Some(RingState::Open));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 4, RingState::Open), ms(40)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_sequence_wrap_is_not_a_gap() {
+        let mut monitor = RingMonitor::new(ms(100));
+        for (n, sequence_id) in [0xfffe, 0xffff, 0x0000, 0x0001].into_iter().enumerate() {
+            let at = ms(n as u64 * 20);
+            assert_eq!(
+                monitor.observe(&test_frame(DOMAIN_A, sequence_id, RingState::Closed), at),
+                vec![]
+            );
+            assert_eq!(
+                monitor.observe(&test_frame(DOMAIN_B, sequence_id, RingState::Closed), at),
+                vec![]
+            );
+        }
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_B, 0x0003, RingState::Closed), ms(80)),
+            vec![RingEvent::SequenceGap {
+                domain_uuid: DOMAIN_B,
+                expected: 0x0002,
+                got: 0x0003,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_ring_state_changes() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 1, RingState::Closed), ms(0));
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 2, RingState::Open), ms(20)),
+            vec![RingEvent::RingStateChanged {
+                domain_uuid: DOMAIN_A,
+                from: RingState::Closed,
+                to: RingState::Open,
+            }]
+        );
+
+        // Non-Test frames advance the sequence but say nothing about the ring state.
+        let mut topology_change = MRPData::new(1);
+        topology_change
+            .push(MRPTLVData::MRPTopologyChange(MRPTopologyChangeData::new(
+                0xa000,
+                sa(),
+                10,
+            )))
+            .unwrap();
+        topology_change
+            .push(MRPTLVData::MRPCommon(MRPCommonData::new(3, DOMAIN_A)))
+            .unwrap();
+        topology_change.push(MRPTLVData::MRPEnd).unwrap();
+        assert_eq!(monitor.observe(&topology_change, ms(30)), vec![]);
+        assert_eq!(
+            monitor.status(&DOMAIN_A).unwrap().ring_state,
+            Some(RingState::Open)
+        );
+        assert_eq!(
+            monitor.observe(&test_frame(DOMAIN_A, 4, RingState::Open), ms(40)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn test_test_timeout() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 1, RingState::Closed), ms(0));
+        monitor.observe(&test_frame(DOMAIN_B, 1, RingState::Closed), ms(50));
+        assert_eq!(monitor.poll(ms(99)), vec![]);
+        assert_eq!(
+            monitor.poll(ms(100)),
+            vec![RingEvent::TestTimeout {
+                domain_uuid: DOMAIN_A,
+                last_test: ms(0),
+            }]
+        );
+        assert_eq!(
+            monitor.poll(ms(200)),
+            vec![RingEvent::TestTimeout {
+                domain_uuid: DOMAIN_B,
+                last_test: ms(50),
+            }]
+        );
+        assert_eq!(monitor.poll(ms(300)), vec![]);
+
+        monitor.observe(&test_frame(DOMAIN_A, 2, RingState::Closed), ms(300));
+        assert!(!monitor.status(&DOMAIN_A).unwrap().test_timed_out);
+        assert_eq!(monitor.poll(ms(350)), vec![]);
+        assert_eq!(monitor.poll(ms(400)).len(), 1);
+    }
+
+    #[test]
+    fn test_average_ring_state_durations() {
+        let mut monitor = RingMonitor::new(ms(100));
+        let mut sequence_id = 0;
+        let mut feed = |monitor: &mut RingMonitor, domain_uuid, ring_state, at| {
+            sequence_id += 1;
+            monitor.observe(&test_frame(domain_uuid, sequence_id, ring_state), ms(at));
+        };
+
+        // Closed since before the first frame: that period's length is unknown.
+        feed(&mut monitor, DOMAIN_A, RingState::Closed, 0);
+        feed(&mut monitor, DOMAIN_A, RingState::Closed, 20);
+        feed(&mut monitor, DOMAIN_A, RingState::Open, 40);
+        assert_eq!(monitor.average_ring_open_duration(), None);
+        assert_eq!(monitor.average_ring_closed_duration(), None);
+
+        // Open 40..100, closed 100..1100, open 1100..1200.
+        feed(&mut monitor, DOMAIN_A, RingState::Open, 60);
+        feed(&mut monitor, DOMAIN_A, RingState::Closed, 100);
+        feed(&mut monitor, DOMAIN_A, RingState::Closed, 600);
+        feed(&mut monitor, DOMAIN_A, RingState::Open, 1100);
+        feed(&mut monitor, DOMAIN_A, RingState::Closed, 1200);
+        assert_eq!(monitor.average_ring_open_duration(), Some(ms(80)));
+        assert_eq!(monitor.average_ring_closed_duration(), Some(ms(1000)));
+        assert_eq!(
+            monitor.status(&DOMAIN_A).unwrap().ring_state_since,
+            Some(ms(1200))
+        );
+
+        // A second domain, open 0..260, joins the averages.
+        feed(&mut monitor, DOMAIN_B, RingState::Open, 0);
+        feed(&mut monitor, DOMAIN_B, RingState::Closed, 0);
+        feed(&mut monitor, DOMAIN_B, RingState::Open, 0);
+        feed(&mut monitor, DOMAIN_B, RingState::Closed, 260);
+        assert_eq!(monitor.average_ring_open_duration(), Some(ms(140)));
+        assert_eq!(monitor.average_ring_closed_duration(), Some(ms(500)));
+    }
+
+    #[test]
+    fn test_flap_count() {
+        let mut monitor = RingMonitor::new(ms(100));
+        monitor.observe(&test_frame(DOMAIN_A, 0, RingState::Closed), ms(0));
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(1000)), 0);
+
+        // Five changes, 10 ms apart, ending at 1050 ms.
+        let states = [RingState::Open, RingState::Closed];
+        for n in 0..5 {
+            let at = 1010 + n as u64 * 10;
+            monitor.observe(&test_frame(DOMAIN_A, n + 1, states[n as usize % 2]), ms(at));
+        }
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(40)), 5);
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(39)), 4);
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(0)), 1);
+        assert!(monitor.is_flapping(&DOMAIN_A, ms(50), 5));
+        assert!(!monitor.is_flapping(&DOMAIN_A, ms(50), 6));
+        assert!(!monitor.is_flapping(&DOMAIN_A, ms(15), 3));
+
+        // Frames without a change, and polls, move the window on.
+        monitor.observe(&test_frame(DOMAIN_A, 6, RingState::Open), ms(1060));
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(40)), 4);
+        monitor.poll(ms(2000));
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(500)), 0);
+        assert!(!monitor.is_flapping(&DOMAIN_A, ms(500), 1));
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(1000)), 5);
+    }
+
+    #[test]
+    fn test_flap_count_per_domain() {
+        let mut monitor = RingMonitor::new(ms(100));
+        for (domain_uuid, at) in [(DOMAIN_A, 0), (DOMAIN_B, 5)] {
+            monitor.observe(&test_frame(domain_uuid, 0, RingState::Closed), ms(at));
+            monitor.observe(&test_frame(domain_uuid, 1, RingState::Open), ms(at + 10));
+        }
+        // Each ring changed state once; neither is flapping.
+        assert_eq!(monitor.flap_count(&DOMAIN_A, ms(100)), 1);
+        assert_eq!(monitor.flap_count(&DOMAIN_B, ms(100)), 1);
+        assert!(!monitor.is_flapping(&DOMAIN_A, ms(100), 2));
+        assert!(!monitor.is_flapping(&DOMAIN_B, ms(100), 2));
+        assert_eq!(monitor.status(&DOMAIN_B).unwrap().state_changes, [ms(15)]);
+
+        monitor.observe(&test_frame(DOMAIN_B, 2, RingState::Closed), ms(20));
+        assert!(monitor.is_flapping(&DOMAIN_B, ms(100), 2));
+        assert!(!monitor.is_flapping(&DOMAIN_A, ms(100), 2));
+        assert_eq!(monitor.flap_count(&Uuid::nil(), Duration::MAX), 0);
+    }
+
+    #[test]
+    fn test_flap_history_is_bounded() {
+        let mut monitor = RingMonitor::new(ms(100));
+        let states = [RingState::Closed, RingState::Open];
+        for n in 0..=STATE_CHANGE_HISTORY as u16 + 10 {
+            monitor.observe(
+                &test_frame(DOMAIN_A, n, states[n as usize % 2]),
+                ms(n.into()),
+            );
+        }
+        assert_eq!(
+            monitor.flap_count(&DOMAIN_A, Duration::MAX),
+            STATE_CHANGE_HISTORY
+        );
+    }
+
+    fn common(domain_uuid: Uuid, sequence_id: u16) -> MRPCommonData {
+        MRPCommonData::new(sequence_id, domain_uuid)
+    }
+
+    #[test]
+    fn test_sequence_tracker_normal_traffic() {
+        let mut tracker = SequenceTracker::new();
+        assert_eq!(tracker.loss_rate(), 0.0);
+        for sequence_id in 100..110 {
+            assert_eq!(tracker.observe(&common(DOMAIN_A, sequence_id)), vec![]);
+            assert_eq!(
+                tracker.observe(&common(DOMAIN_B, 1000 + sequence_id)),
+                vec![]
+            );
+        }
+        assert_eq!(tracker.total_observed(), 20);
+        assert_eq!(tracker.loss_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_sequence_tracker_gaps() {
+        let mut tracker = SequenceTracker::new();
+        tracker.observe(&common(DOMAIN_A, 1));
+        tracker.observe(&common(DOMAIN_B, 50));
+        let gaps = tracker.observe(&common(DOMAIN_A, 5));
+        assert_eq!(
+            gaps,
+            vec![SequenceGap {
+                domain_uuid: DOMAIN_A,
+                expected: 2,
+                got: 5,
+            }]
+        );
+        assert_eq!(gaps[0].missing(), 3);
+        assert_eq!(tracker.observe(&common(DOMAIN_B, 51)), vec![]);
+        // 4 observed, 3 lost.
+        assert_eq!(tracker.loss_rate(), 3.0 / 7.0);
+
+        // Duplicates and late arrivals are neither gaps nor rewinds.
+        assert_eq!(tracker.observe(&common(DOMAIN_A, 5)), vec![]);
+        assert_eq!(tracker.observe(&common(DOMAIN_A, 3)), vec![]);
+        assert_eq!(tracker.observe(&common(DOMAIN_A, 6)), vec![]);
+        // ID 3 was already counted as lost and 5 as observed; only 6 is new.
+        assert_eq!(tracker.total_observed(), 5);
+        assert_eq!(tracker.loss_rate(), 3.0 / 8.0);
+    }
+
+    #[test]
+    fn test_sequence_tracker_wraparound() {
+        let mut tracker = SequenceTracker::new();
+        for sequence_id in [0xfffe, 0xffff, 0x0000, 0x0001] {
+            assert_eq!(tracker.observe(&common(DOMAIN_A, sequence_id)), vec![]);
+        }
+        let gaps = tracker.observe(&common(DOMAIN_A, 0x0004));
+        assert_eq!(gaps[0].missing(), 2);
+
+        tracker.observe(&common(DOMAIN_B, 0xfffd));
+        let gaps = tracker.observe(&common(DOMAIN_B, 0x0001));
+        assert_eq!(
+            gaps,
+            vec![SequenceGap {
+                domain_uuid: DOMAIN_B,
+                expected: 0xfffe,
+                got: 0x0001,
+            }]
+        );
+        assert_eq!(gaps[0].missing(), 3);
+        assert_eq!(tracker.total_observed(), 7);
+    }
+}
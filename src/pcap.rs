@@ -0,0 +1,608 @@
+use crate::frame::{parse_mrp_frame, FrameError};
+use crate::transmit::{check_for_transmit, TransmitCheck, Violation};
+use crate::{read_or_eof, MRPData, MRPParseError, MRPTLVData, MacAddress};
+use core::fmt::{self, Write};
+use std::io::{self, Read};
+use std::time::Duration;
+use std::vec::Vec;
+
+/// LINKTYPE_ETHERNET; packets of any other link type are skipped.
+const LINKTYPE_ETHERNET: u32 = 1;
+
+/// Upper bound on a record or block, so a corrupt length cannot trigger a huge allocation.
+const MAX_RECORD_LEN: usize = 16 * 1024 * 1024;
+
+const PCAPNG_SECTION_HEADER: u32 = 0x0a0d_0d0a;
+const PCAPNG_INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const PCAPNG_SIMPLE_PACKET: u32 = 0x0000_0003;
+const PCAPNG_ENHANCED_PACKET: u32 = 0x0000_0006;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacketMeta {
+    /// Capture time since the Unix epoch; zero for pcapng Simple Packet Blocks, which have none.
+    pub timestamp: Duration,
+    /// 1-based position of the packet in the capture, as Wireshark numbers it.
+    pub frame_number: u64,
+    pub src: MacAddress,
+    pub dst: MacAddress,
+}
+
+#[derive(Debug)]
+pub enum PcapMrpError {
+    Io(io::Error),
+    /// The input is not a pcap or pcapng capture, or a record header is corrupt.
+    InvalidFormat,
+    /// A pcap capture whose link type is not Ethernet.
+    UnsupportedLinkType(u32),
+    /// An MRP frame whose payload does not parse. Iteration continues with the next packet.
+    Malformed {
+        frame_number: u64,
+        error: MRPParseError,
+    },
+}
+
+impl fmt::Display for PcapMrpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PcapMrpError::Io(err) => write!(f, "failed to read capture: {}", err),
+            PcapMrpError::InvalidFormat => write!(f, "not a valid pcap or pcapng capture"),
+            PcapMrpError::UnsupportedLinkType(link_type) => {
+                write!(f, "unsupported capture link type {}", link_type)
+            }
+            PcapMrpError::Malformed {
+                frame_number,
+                error,
+            } => write!(
+                f,
+                "frame {}: malformed MRP payload: {}",
+                frame_number, error
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PcapMrpError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PcapMrpError::Io(err) => Some(err),
+            PcapMrpError::Malformed { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PcapMrpError {
+    fn from(err: io::Error) -> Self {
+        PcapMrpError::Io(err)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Interface {
+    link_type: u32,
+    /// Timestamp units per second.
+    units_per_second: u64,
+}
+
+#[derive(Debug)]
+enum Format {
+    Pcap {
+        big_endian: bool,
+        nanos: bool,
+    },
+    PcapNg {
+        big_endian: bool,
+        interfaces: Vec<Interface>,
+    },
+}
+
+/// Iterator returned by [`read_mrp_from_pcap`].
+#[derive(Debug)]
+pub struct PcapMrpReader<R> {
+    reader: R,
+    format: Option<Format>,
+    frame_number: u64,
+    done: bool,
+}
+
+/// Yields the MRP frames of a pcap or pcapng capture of Ethernet traffic, in capture order.
+///
+/// Non-MRP frames are skipped. A malformed MRP frame is reported as
+/// [`PcapMrpError::Malformed`] and iteration continues; any other error ends the iteration.
+pub fn read_mrp_from_pcap<R: Read>(reader: R) -> PcapMrpReader<R> {
+    PcapMrpReader {
+        reader,
+        format: None,
+        frame_number: 0,
+        done: false,
+    }
+}
+
+/// Renders frames read by [`read_mrp_from_pcap`] as InfluxDB line protocol, stamped with the
+/// capture time in nanoseconds.
+///
+/// Each frame yields up to three points, all tagged with `domain_uuid` when the frame has an
+/// MRP_Common TLV: `mrp_ring_state` (field `state`, the wire value: 0 open, 1 closed) for
+/// Test frames, `mrp_sequence_id` (field `value`), and `mrp_prio` (field `value`) for Test and
+/// TopologyChange frames.
+pub fn to_influxdb_line_protocol(frames: &[(PacketMeta, MRPData)]) -> String {
+    let mut lines = String::new();
+    for (meta, mrp_data) in frames {
+        let tags = match mrp_data.domain_uuid() {
+            Some(domain_uuid) => format!(",domain_uuid={}", domain_uuid),
+            None => String::new(),
+        };
+        let at = meta.timestamp.as_nanos();
+        if let Some(test) = mrp_data.test() {
+            let state = test.ring_state_numeric();
+            let _ = writeln!(lines, "mrp_ring_state{} state={}i {}", tags, state, at);
+        }
+        if let Some(sequence_id) = mrp_data.sequence_id() {
+            let _ = writeln!(
+                lines,
+                "mrp_sequence_id{} value={}i {}",
+                tags, sequence_id, at
+            );
+        }
+        let prio = mrp_data.iter().find_map(|header| match &header.data {
+            MRPTLVData::MRPTest(test) => Some(test.prio),
+            MRPTLVData::MRPTopologyChange(topology_change) => Some(topology_change.prio),
+            _ => None,
+        });
+        if let Some(prio) = prio {
+            let _ = writeln!(lines, "mrp_prio{} value={}i {}", tags, prio, at);
+        }
+    }
+    lines
+}
+
+/// Receives frames played back by [`replay`].
+pub trait MRPFrameSink {
+    fn on_frame(&mut self, meta: &PacketMeta, mrp_data: &MRPData);
+}
+
+impl<F: FnMut(&PacketMeta, &MRPData)> MRPFrameSink for F {
+    fn on_frame(&mut self, meta: &PacketMeta, mrp_data: &MRPData) {
+        self(meta, mrp_data)
+    }
+}
+
+/// A frame [`replay`] refused to send; every frame before it was delivered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RejectedFrame {
+    pub frame_number: u64,
+    pub violations: Vec<Violation>,
+}
+
+/// Feeds frames read by [`read_mrp_from_pcap`] to `sink`, sleeping between them for the gap
+/// between their capture timestamps.
+///
+/// Unless `check` is [`TransmitCheck::Skip`], each frame goes through
+/// [`check_for_transmit`] first and replay stops at the first one that fails.
+///
+/// Each gap is scaled by `speed_multiplier`: 1.0 replays in real time, 0.5 twice as fast, and
+/// 0.0 (or any non-positive value) delivers every frame immediately. Frames whose timestamp
+/// does not advance are delivered without a pause, and so are frames whose scaled gap is not
+/// a representable [`Duration`], e.g. with an infinite or NaN multiplier.
+pub fn replay<S: MRPFrameSink + ?Sized>(
+    frames: &[(PacketMeta, MRPData)],
+    sink: &mut S,
+    speed_multiplier: f64,
+    check: TransmitCheck,
+) -> Result<(), RejectedFrame> {
+    let mut previous: Option<Duration> = None;
+    for (meta, mrp_data) in frames {
+        if let Some(previous) = previous {
+            let gap = meta.timestamp.saturating_sub(previous);
+            if speed_multiplier > 0.0 && !gap.is_zero() {
+                if let Ok(pause) = Duration::try_from_secs_f64(gap.as_secs_f64() * speed_multiplier)
+                {
+                    std::thread::sleep(pause);
+                }
+            }
+        }
+        previous = Some(meta.timestamp);
+        check_for_transmit(mrp_data, meta.dst, check).map_err(|violations| RejectedFrame {
+            frame_number: meta.frame_number,
+            violations,
+        })?;
+        sink.on_frame(meta, mrp_data);
+    }
+    Ok(())
+}
+
+fn u16_at(bytes: &[u8], offset: usize, big_endian: bool) -> u16 {
+    let bytes = [bytes[offset], bytes[offset + 1]];
+    if big_endian {
+        u16::from_be_bytes(bytes)
+    } else {
+        u16::from_le_bytes(bytes)
+    }
+}
+
+fn u32_at(bytes: &[u8], offset: usize, big_endian: bool) -> u32 {
+    let bytes = [
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ];
+    if big_endian {
+        u32::from_be_bytes(bytes)
+    } else {
+        u32::from_le_bytes(bytes)
+    }
+}
+
+fn read_vec<R: Read>(reader: &mut R, len: usize) -> Result<Vec<u8>, PcapMrpError> {
+    if len > MAX_RECORD_LEN {
+        return Err(PcapMrpError::InvalidFormat);
+    }
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+fn timestamp(units: u64, units_per_second: u64) -> Duration {
+    let nanos = (units % units_per_second) as u128 * 1_000_000_000 / units_per_second as u128;
+    Duration::new(units / units_per_second, nanos as u32)
+}
+
+/// Decodes the `if_tsresol` option of an Interface Description Block body; microseconds by default.
+fn units_per_second(body: &[u8], big_endian: bool) -> Result<u64, PcapMrpError> {
+    let mut offset = 8;
+    while offset + 4 <= body.len() {
+        let code = u16_at(body, offset, big_endian);
+        let len = u16_at(body, offset + 2, big_endian) as usize;
+        if code == 0 {
+            break;
+        }
+        if code == 9 && len == 1 && offset + 5 <= body.len() {
+            let resolution = body[offset + 4];
+            let exponent = u32::from(resolution & 0x7f);
+            return if resolution & 0x80 == 0 {
+                10u64.checked_pow(exponent)
+            } else {
+                1u64.checked_shl(exponent)
+            }
+            .ok_or(PcapMrpError::InvalidFormat);
+        }
+        offset += 4 + len.next_multiple_of(4);
+    }
+    Ok(1_000_000)
+}
+
+/// Reads the rest of a pcapng Section Header Block, after its type and total length.
+fn read_section<R: Read>(reader: &mut R, total_len: [u8; 4]) -> Result<Format, PcapMrpError> {
+    let mut byte_order = [0u8; 4];
+    reader.read_exact(&mut byte_order)?;
+    let big_endian = match byte_order {
+        [0x1a, 0x2b, 0x3c, 0x4d] => true,
+        [0x4d, 0x3c, 0x2b, 0x1a] => false,
+        _ => return Err(PcapMrpError::InvalidFormat),
+    };
+    let total_len = u32_at(&total_len, 0, big_endian) as usize;
+    if total_len < 16 || !total_len.is_multiple_of(4) {
+        return Err(PcapMrpError::InvalidFormat);
+    }
+    read_vec(reader, total_len - 12)?;
+    Ok(Format::PcapNg {
+        big_endian,
+        interfaces: Vec::new(),
+    })
+}
+
+impl<R: Read> PcapMrpReader<R> {
+    fn open(&mut self) -> Result<Format, PcapMrpError> {
+        let mut magic = [0u8; 4];
+        self.reader.read_exact(&mut magic)?;
+        let (big_endian, nanos) = match magic {
+            [0xd4, 0xc3, 0xb2, 0xa1] => (false, false),
+            [0x4d, 0x3c, 0xb2, 0xa1] => (false, true),
+            [0xa1, 0xb2, 0xc3, 0xd4] => (true, false),
+            [0xa1, 0xb2, 0x3c, 0x4d] => (true, true),
+            [0x0a, 0x0d, 0x0d, 0x0a] => {
+                let mut total_len = [0u8; 4];
+                self.reader.read_exact(&mut total_len)?;
+                return read_section(&mut self.reader, total_len);
+            }
+            _ => return Err(PcapMrpError::InvalidFormat),
+        };
+        let mut header = [0u8; 20];
+        self.reader.read_exact(&mut header)?;
+        let link_type = u32_at(&header, 16, big_endian);
+        if link_type != LINKTYPE_ETHERNET {
+            return Err(PcapMrpError::UnsupportedLinkType(link_type));
+        }
+        Ok(Format::Pcap { big_endian, nanos })
+    }
+
+    /// The next Ethernet packet with its timestamp, or `None` at the end of the capture.
+    fn next_packet(&mut self) -> Result<Option<(Duration, Vec<u8>)>, PcapMrpError> {
+        loop {
+            if self.format.is_none() {
+                self.format = Some(self.open()?);
+            }
+            let format = self.format.as_mut().expect("capture header was read above");
+            match &mut *format {
+                Format::Pcap { big_endian, nanos } => {
+                    let (big_endian, nanos) = (*big_endian, *nanos);
+                    let mut header = [0u8; 16];
+                    if !read_or_eof(&mut self.reader, &mut header)? {
+                        return Ok(None);
+                    }
+                    let seconds = u32_at(&header, 0, big_endian) as u64;
+                    let fraction = u32_at(&header, 4, big_endian);
+                    let captured = u32_at(&header, 8, big_endian) as usize;
+                    let packet = read_vec(&mut self.reader, captured)?;
+                    let fraction = if nanos {
+                        fraction
+                    } else {
+                        fraction.saturating_mul(1000)
+                    };
+                    self.frame_number += 1;
+                    return Ok(Some((
+                        Duration::from_secs(seconds) + Duration::from_nanos(fraction as u64),
+                        packet,
+                    )));
+                }
+                Format::PcapNg {
+                    big_endian,
+                    interfaces,
+                } => {
+                    let big_endian = *big_endian;
+                    let mut header = [0u8; 8];
+                    if !read_or_eof(&mut self.reader, &mut header)? {
+                        return Ok(None);
+                    }
+                    let block_type = u32_at(&header, 0, big_endian);
+                    if block_type == PCAPNG_SECTION_HEADER {
+                        // A new section may switch byte order and starts with no interfaces.
+                        let total_len = [header[4], header[5], header[6], header[7]];
+                        *format = read_section(&mut self.reader, total_len)?;
+                        continue;
+                    }
+
+                    let total_len = u32_at(&header, 4, big_endian) as usize;
+                    if total_len < 12 || !total_len.is_multiple_of(4) {
+                        return Err(PcapMrpError::InvalidFormat);
+                    }
+                    let block = read_vec(&mut self.reader, total_len - 8)?;
+                    let body = &block[..block.len() - 4];
+                    match block_type {
+                        PCAPNG_INTERFACE_DESCRIPTION if body.len() >= 8 => {
+                            interfaces.push(Interface {
+                                link_type: u32::from(u16_at(body, 0, big_endian)),
+                                units_per_second: units_per_second(body, big_endian)?,
+                            });
+                        }
+                        PCAPNG_ENHANCED_PACKET if body.len() >= 20 => {
+                            self.frame_number += 1;
+                            let interface = *interfaces
+                                .get(u32_at(body, 0, big_endian) as usize)
+                                .ok_or(PcapMrpError::InvalidFormat)?;
+                            let units = (u64::from(u32_at(body, 4, big_endian)) << 32)
+                                | u64::from(u32_at(body, 8, big_endian));
+                            let captured = u32_at(body, 12, big_endian) as usize;
+                            let packet = body[20..]
+                                .get(..captured)
+                                .ok_or(PcapMrpError::InvalidFormat)?;
+                            if interface.link_type == LINKTYPE_ETHERNET {
+                                return Ok(Some((
+                                    timestamp(units, interface.units_per_second),
+                                    packet.to_vec(),
+                                )));
+                            }
+                        }
+                        PCAPNG_SIMPLE_PACKET if body.len() >= 4 => {
+                            self.frame_number += 1;
+                            let interface =
+                                *interfaces.first().ok_or(PcapMrpError::InvalidFormat)?;
+                            let original = u32_at(body, 0, big_endian) as usize;
+                            let packet = &body[4..];
+                            let packet = &packet[..original.min(packet.len())];
+                            if interface.link_type == LINKTYPE_ETHERNET {
+                                return Ok(Some((Duration::ZERO, packet.to_vec())));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for PcapMrpReader<R> {
+    type Item = Result<(PacketMeta, MRPData), PcapMrpError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let (timestamp, packet) = match self.next_packet() {
+                Ok(Some(packet)) => packet,
+                Ok(None) => break,
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            };
+            match parse_mrp_frame(&packet) {
+                Ok(frame) => {
+                    let meta = PacketMeta {
+                        timestamp,
+                        frame_number: self.frame_number,
+                        src: frame.src,
+                        dst: frame.dst,
+                    };
+                    return Some(Ok((meta, frame.data)));
+                }
+                Err(FrameError::Malformed(error)) => {
+                    return Some(Err(PcapMrpError::Malformed {
+                        frame_number: self.frame_number,
+                        error,
+                    }));
+                }
+                Err(_) => {}
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transmit::{MC_CONTROL, MC_TEST};
+    use crate::MrpFrameKind;
+
+    const PCAP: &[u8] = include_bytes!("../tests/fixtures/mrp_mixed.pcap");
+    const PCAPNG: &[u8] = include_bytes!("../tests/fixtures/mrp_mixed.pcapng");
+
+    fn collect(capture: &[u8]) -> Vec<(PacketMeta, MRPData)> {
+        read_mrp_from_pcap(capture)
+            .map(|item| item.unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_reads_mrp_frames_in_order() {
+        for capture in [PCAP, PCAPNG] {
+            let frames = collect(capture);
+            assert_eq!(frames.len(), 2);
+
+            let (meta, test) = &frames[0];
+            assert_eq!(meta.frame_number, 1);
+            assert_eq!(meta.timestamp, Duration::new(1_700_000_000, 100_000));
+            assert_eq!(meta.dst, MC_TEST);
+            assert_eq!(meta.src.to_string(), "00:0e:8c:e0:2f:22");
+            assert_eq!(test.frame_kind(), MrpFrameKind::Test);
+            assert_eq!(test.sequence_id(), Some(0x057e));
+
+            let (meta, topology_change) = &frames[1];
+            assert_eq!(meta.frame_number, 3);
+            assert_eq!(meta.timestamp, Duration::new(1_700_000_000, 20_100_000));
+            assert_eq!(meta.dst, MC_CONTROL);
+            assert_eq!(topology_change.frame_kind(), MrpFrameKind::TopologyChange);
+            assert_eq!(topology_change.sequence_id(), Some(0x057f));
+        }
+    }
+
+    #[test]
+    fn test_malformed_frame_does_not_stop_iteration() {
+        let mut capture = PCAP.to_vec();
+        // Cut the Test TLV length short so the first MRP frame no longer parses.
+        capture[24 + 16 + 14 + 3] = 0x04;
+        let items: Vec<_> = read_mrp_from_pcap(&capture[..]).collect();
+        assert_eq!(items.len(), 2);
+        assert!(matches!(
+            items[0],
+            Err(PcapMrpError::Malformed {
+                frame_number: 1,
+                error: MRPParseError::InvalidTlvLength { .. },
+            })
+        ));
+        assert_eq!(items[1].as_ref().unwrap().0.frame_number, 3);
+    }
+
+    #[test]
+    fn test_capture_errors() {
+        let items: Vec<_> = read_mrp_from_pcap(&b"not a capture"[..]).collect();
+        assert!(matches!(items[..], [Err(PcapMrpError::InvalidFormat)]));
+
+        let mut raw_ip = PCAP[..24].to_vec();
+        raw_ip[20] = 101;
+        let items: Vec<_> = read_mrp_from_pcap(&raw_ip[..]).collect();
+        assert!(matches!(
+            items[..],
+            [Err(PcapMrpError::UnsupportedLinkType(101))]
+        ));
+
+        // A capture cut off inside a record yields what came before, then the I/O error.
+        let items: Vec<_> = read_mrp_from_pcap(&PCAP[..PCAP.len() - 10]).collect();
+        assert_eq!(items.len(), 3);
+        assert!(matches!(items[2], Err(PcapMrpError::Io(_))));
+    }
+
+    #[test]
+    fn test_replay_immediate() {
+        let frames = collect(PCAPNG);
+        let mut delivered = Vec::new();
+        replay(
+            &frames,
+            &mut |meta: &PacketMeta, mrp_data: &MRPData| {
+                delivered.push((meta.clone(), mrp_data.clone()))
+            },
+            0.0,
+            TransmitCheck::Enforce,
+        )
+        .unwrap();
+        assert_eq!(delivered, frames);
+    }
+
+    #[test]
+    fn test_replay_checks_frames() {
+        let mut frames = collect(PCAPNG);
+        frames[1].1.tlv_headers.remove(1);
+        let mut delivered = Vec::new();
+        let mut sink = |meta: &PacketMeta, _: &MRPData| delivered.push(meta.frame_number);
+        assert_eq!(
+            replay(&frames, &mut sink, 0.0, TransmitCheck::Enforce),
+            Err(RejectedFrame {
+                frame_number: 3,
+                violations: vec![Violation::MissingCommon],
+            })
+        );
+        assert_eq!(replay(&frames, &mut sink, 0.0, TransmitCheck::Skip), Ok(()));
+        assert_eq!(delivered, [1, 1, 3]);
+    }
+
+    #[test]
+    fn test_replay_unrepresentable_pause() {
+        let mut frames = collect(PCAPNG);
+        frames[1].0.timestamp = frames[0].0.timestamp + Duration::from_secs(3);
+        for speed_multiplier in [f64::INFINITY, f64::MAX, f64::NAN] {
+            let mut delivered = 0;
+            replay(
+                &frames,
+                &mut |_: &PacketMeta, _: &MRPData| delivered += 1,
+                speed_multiplier,
+                TransmitCheck::Enforce,
+            )
+            .unwrap();
+            assert_eq!(delivered, frames.len());
+        }
+    }
+
+    #[test]
+    fn test_influxdb_line_protocol() {
+        let lines = to_influxdb_line_protocol(&collect(PCAP));
+        let domain = "domain_uuid=c3d687fe-789e-03a1-acdb-e5bfcbbc27b6";
+        for measurement in ["mrp_ring_state", "mrp_sequence_id", "mrp_prio"] {
+            assert!(
+                lines.lines().any(|line| line.starts_with(measurement)),
+                "{} not in\n{}",
+                measurement,
+                lines
+            );
+        }
+        let test_prio = lines
+            .lines()
+            .find(|line| line.starts_with("mrp_prio"))
+            .unwrap();
+        assert_eq!(
+            test_prio,
+            format!("mrp_prio,{} value=40960i 1700000000000100000", domain)
+        );
+        // The TopologyChange frame has a sequence ID and priority but no ring state.
+        assert_eq!(
+            lines
+                .lines()
+                .filter(|line| line.starts_with("mrp_ring_state"))
+                .count(),
+            1
+        );
+        assert_eq!(lines.lines().count(), 5);
+        assert_eq!(to_influxdb_line_protocol(&[]), "");
+    }
+}
@@ -0,0 +1,379 @@
+use crate::{
+    parse_tlv_data, MRPCommonData, MRPData, MRPInLinkData, MRPInLinkStatusPollData, MRPInTestData,
+    MRPInTopologyChangeData, MRPLinkData, MRPTLVData, MRPTLVHeader, MRPTestData,
+    MRPTopologyChangeData, MacAddress, UnknownTlvPolicy,
+};
+use prost::{DecodeError, Message};
+use uuid::Uuid;
+
+/// The `mrp.MrpData` message from `proto/mrp.proto`.
+#[derive(Clone, PartialEq, Message)]
+pub struct MrpData {
+    #[prost(uint32, tag = "1")]
+    pub version: u32,
+    #[prost(message, repeated, tag = "2")]
+    pub tlvs: Vec<Tlv>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Tlv {
+    #[prost(uint32, tag = "1")]
+    pub tlv_type: u32,
+    #[prost(uint32, tag = "2")]
+    pub length: u32,
+    #[prost(oneof = "Body", tags = "3, 4, 5, 6, 7, 8, 9, 10, 15")]
+    pub body: Option<Body>,
+}
+
+#[derive(Clone, PartialEq, prost::Oneof)]
+pub enum Body {
+    #[prost(message, tag = "3")]
+    Test(Test),
+    #[prost(message, tag = "4")]
+    Common(Common),
+    #[prost(message, tag = "5")]
+    TopologyChange(TopologyChange),
+    #[prost(message, tag = "6")]
+    Link(Link),
+    #[prost(message, tag = "7")]
+    InTest(InTest),
+    #[prost(message, tag = "8")]
+    InTopologyChange(InTopologyChange),
+    #[prost(message, tag = "9")]
+    InLink(InLink),
+    #[prost(message, tag = "10")]
+    InLinkStatusPoll(InLinkStatusPoll),
+    /// Value bytes of MRP_Option, MRP_End and unknown TLVs.
+    #[prost(bytes, tag = "15")]
+    Raw(Vec<u8>),
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Test {
+    #[prost(uint32, tag = "1")]
+    pub prio: u32,
+    #[prost(bytes, tag = "2")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub port_role: u32,
+    #[prost(uint32, tag = "4")]
+    pub ring_state: u32,
+    #[prost(uint32, tag = "5")]
+    pub transition: u32,
+    #[prost(uint32, tag = "6")]
+    pub timestamp: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Common {
+    #[prost(uint32, tag = "1")]
+    pub sequence_id: u32,
+    #[prost(bytes, tag = "2")]
+    pub domain_uuid: Vec<u8>,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct TopologyChange {
+    #[prost(uint32, tag = "1")]
+    pub prio: u32,
+    #[prost(bytes, tag = "2")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub interval: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct Link {
+    #[prost(bytes, tag = "1")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub port_role: u32,
+    #[prost(uint32, tag = "3")]
+    pub interval: u32,
+    #[prost(uint32, tag = "4")]
+    pub blocked: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InTest {
+    #[prost(uint32, tag = "1")]
+    pub in_id: u32,
+    #[prost(bytes, tag = "2")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "3")]
+    pub port_role: u32,
+    #[prost(uint32, tag = "4")]
+    pub in_state: u32,
+    #[prost(uint32, tag = "5")]
+    pub transition: u32,
+    #[prost(uint32, tag = "6")]
+    pub timestamp: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InTopologyChange {
+    #[prost(bytes, tag = "1")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub in_id: u32,
+    #[prost(uint32, tag = "3")]
+    pub interval: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InLink {
+    #[prost(bytes, tag = "1")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub port_role: u32,
+    #[prost(uint32, tag = "3")]
+    pub in_id: u32,
+    #[prost(uint32, tag = "4")]
+    pub interval: u32,
+    #[prost(uint32, tag = "5")]
+    pub link_info: u32,
+}
+
+#[derive(Clone, PartialEq, Message)]
+pub struct InLinkStatusPoll {
+    #[prost(bytes, tag = "1")]
+    pub sa: Vec<u8>,
+    #[prost(uint32, tag = "2")]
+    pub port_role: u32,
+    #[prost(uint32, tag = "3")]
+    pub in_id: u32,
+}
+
+impl From<&MRPTLVHeader> for Tlv {
+    fn from(header: &MRPTLVHeader) -> Self {
+        let body = match &header.data {
+            MRPTLVData::MRPTest(data) => Body::Test(Test {
+                prio: data.prio.into(),
+                sa: data.sa.0.to_vec(),
+                port_role: u16::from(data.port_role).into(),
+                ring_state: u16::from(data.ring_state).into(),
+                transition: data.transition.into(),
+                timestamp: data.timestamp,
+            }),
+            MRPTLVData::MRPCommon(data) => Body::Common(Common {
+                sequence_id: data.sequence_id.into(),
+                domain_uuid: data.domain_uuid.as_bytes().to_vec(),
+            }),
+            MRPTLVData::MRPTopologyChange(data) => Body::TopologyChange(TopologyChange {
+                prio: data.prio.into(),
+                sa: data.sa.0.to_vec(),
+                interval: data.interval.into(),
+            }),
+            MRPTLVData::MRPLinkDown(data) | MRPTLVData::MRPLinkUp(data) => Body::Link(Link {
+                sa: data.sa.0.to_vec(),
+                port_role: u16::from(data.port_role).into(),
+                interval: data.interval.into(),
+                blocked: data.blocked.into(),
+            }),
+            MRPTLVData::MRPInTest(data) => Body::InTest(InTest {
+                in_id: data.in_id.into(),
+                sa: data.sa.0.to_vec(),
+                port_role: u16::from(data.port_role).into(),
+                in_state: u16::from(data.in_state).into(),
+                transition: data.transition.into(),
+                timestamp: data.timestamp,
+            }),
+            MRPTLVData::MRPInTopologyChange(data) => Body::InTopologyChange(InTopologyChange {
+                sa: data.sa.0.to_vec(),
+                in_id: data.in_id.into(),
+                interval: data.interval.into(),
+            }),
+            MRPTLVData::MRPInLinkDown(data) | MRPTLVData::MRPInLinkUp(data) => {
+                Body::InLink(InLink {
+                    sa: data.sa.0.to_vec(),
+                    port_role: u16::from(data.port_role).into(),
+                    in_id: data.in_id.into(),
+                    interval: data.interval.into(),
+                    link_info: data.link_info.into(),
+                })
+            }
+            MRPTLVData::MRPInLinkStatusPoll(data) => Body::InLinkStatusPoll(InLinkStatusPoll {
+                sa: data.sa.0.to_vec(),
+                port_role: u16::from(data.port_role).into(),
+                in_id: data.in_id.into(),
+            }),
+            MRPTLVData::MRPOption(_) | MRPTLVData::MRPEnd | MRPTLVData::Unknown { .. } => {
+                let mut raw = Vec::new();
+                header.data.write_payload(&mut raw);
+                Body::Raw(raw)
+            }
+        };
+        Tlv {
+            tlv_type: header.tlv_type.into(),
+            length: header.length.into(),
+            body: Some(body),
+        }
+    }
+}
+
+fn narrow<T: TryFrom<u32>>(value: u32, message: &'static str) -> Result<T, DecodeError> {
+    T::try_from(value).map_err(|_| DecodeError::new(message))
+}
+
+fn mac(bytes: &[u8]) -> Result<MacAddress, DecodeError> {
+    MacAddress::try_from(bytes).map_err(|_| DecodeError::new("MAC address must be 6 bytes"))
+}
+
+impl TryFrom<&Tlv> for MRPTLVHeader {
+    type Error = DecodeError;
+
+    fn try_from(tlv: &Tlv) -> Result<Self, Self::Error> {
+        let tlv_type: u8 = narrow(tlv.tlv_type, "tlv_type out of range")?;
+        let data = match tlv.body.as_ref() {
+            Some(Body::Test(test)) => MRPTLVData::MRPTest(MRPTestData {
+                prio: narrow(test.prio, "prio out of range")?,
+                sa: mac(&test.sa)?,
+                port_role: narrow::<u16>(test.port_role, "port_role out of range")?.into(),
+                ring_state: narrow::<u16>(test.ring_state, "ring_state out of range")?.into(),
+                transition: narrow(test.transition, "transition out of range")?,
+                timestamp: test.timestamp,
+            }),
+            Some(Body::Common(common)) => MRPTLVData::MRPCommon(MRPCommonData {
+                sequence_id: narrow(common.sequence_id, "sequence_id out of range")?,
+                domain_uuid: Uuid::from_slice(&common.domain_uuid)
+                    .map_err(|_| DecodeError::new("domain_uuid must be 16 bytes"))?,
+            }),
+            Some(Body::TopologyChange(tc)) => {
+                MRPTLVData::MRPTopologyChange(MRPTopologyChangeData {
+                    prio: narrow(tc.prio, "prio out of range")?,
+                    sa: mac(&tc.sa)?,
+                    interval: narrow(tc.interval, "interval out of range")?,
+                })
+            }
+            Some(Body::Link(link)) => {
+                let link_data = MRPLinkData {
+                    sa: mac(&link.sa)?,
+                    port_role: narrow::<u16>(link.port_role, "port_role out of range")?.into(),
+                    interval: narrow(link.interval, "interval out of range")?,
+                    blocked: narrow(link.blocked, "blocked out of range")?,
+                };
+                match tlv_type {
+                    0x04 => MRPTLVData::MRPLinkDown(link_data),
+                    0x05 => MRPTLVData::MRPLinkUp(link_data),
+                    _ => return Err(DecodeError::new("link body needs tlv_type 0x04 or 0x05")),
+                }
+            }
+            Some(Body::InTest(test)) => MRPTLVData::MRPInTest(MRPInTestData {
+                in_id: narrow(test.in_id, "in_id out of range")?,
+                sa: mac(&test.sa)?,
+                port_role: narrow::<u16>(test.port_role, "port_role out of range")?.into(),
+                in_state: narrow::<u16>(test.in_state, "in_state out of range")?.into(),
+                transition: narrow(test.transition, "transition out of range")?,
+                timestamp: test.timestamp,
+            }),
+            Some(Body::InTopologyChange(tc)) => {
+                MRPTLVData::MRPInTopologyChange(MRPInTopologyChangeData {
+                    sa: mac(&tc.sa)?,
+                    in_id: narrow(tc.in_id, "in_id out of range")?,
+                    interval: narrow(tc.interval, "interval out of range")?,
+                })
+            }
+            Some(Body::InLink(link)) => {
+                let link_data = MRPInLinkData {
+                    sa: mac(&link.sa)?,
+                    port_role: narrow::<u16>(link.port_role, "port_role out of range")?.into(),
+                    in_id: narrow(link.in_id, "in_id out of range")?,
+                    interval: narrow(link.interval, "interval out of range")?,
+                    link_info: narrow(link.link_info, "link_info out of range")?,
+                };
+                match tlv_type {
+                    0x08 => MRPTLVData::MRPInLinkDown(link_data),
+                    0x09 => MRPTLVData::MRPInLinkUp(link_data),
+                    _ => return Err(DecodeError::new("in_link body needs tlv_type 0x08 or 0x09")),
+                }
+            }
+            Some(Body::InLinkStatusPoll(poll)) => {
+                MRPTLVData::MRPInLinkStatusPoll(MRPInLinkStatusPollData {
+                    sa: mac(&poll.sa)?,
+                    port_role: narrow::<u16>(poll.port_role, "port_role out of range")?.into(),
+                    in_id: narrow(poll.in_id, "in_id out of range")?,
+                })
+            }
+            Some(Body::Raw(raw)) => parse_tlv_data(tlv_type, raw, UnknownTlvPolicy::Keep)
+                .map_err(|_| DecodeError::new("raw TLV value does not parse"))?,
+            None => return Err(DecodeError::new("TLV has no body")),
+        };
+        Ok(MRPTLVHeader {
+            tlv_type,
+            length: narrow(tlv.length, "length out of range")?,
+            data,
+        })
+    }
+}
+
+impl From<&MRPData> for MrpData {
+    fn from(mrp_data: &MRPData) -> Self {
+        MrpData {
+            version: mrp_data.version.into(),
+            tlvs: mrp_data.iter().map(Tlv::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<&MrpData> for MRPData {
+    type Error = DecodeError;
+
+    fn try_from(message: &MrpData) -> Result<Self, Self::Error> {
+        Ok(MRPData {
+            version: narrow(message.version, "version out of range")?,
+            tlv_headers: message
+                .tlvs
+                .iter()
+                .map(MRPTLVHeader::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl MRPData {
+    /// Encodes the parsed structure as a `mrp.MrpData` protobuf message.
+    pub fn to_proto_bytes(&self) -> Vec<u8> {
+        MrpData::from(self).encode_to_vec()
+    }
+
+    pub fn from_proto_bytes(bytes: &[u8]) -> Result<MRPData, DecodeError> {
+        MRPData::try_from(&MrpData::decode(bytes)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_mrp_data;
+    use crate::TEST_PAYLOAD;
+
+    #[test]
+    fn test_proto_round_trip() {
+        let mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        let bytes = mrp_data.to_proto_bytes();
+        assert_eq!(MRPData::from_proto_bytes(&bytes).unwrap(), mrp_data);
+
+        let message = MrpData::decode(bytes.as_slice()).unwrap();
+        assert_eq!(message.version, 1);
+        assert!(matches!(message.tlvs[0].body, Some(Body::Test(_))));
+        assert_eq!(
+            message.tlvs[2].body,
+            Some(Body::Raw(TEST_PAYLOAD[44..50].to_vec()))
+        );
+    }
+
+    #[test]
+    fn test_proto_rejects_bad_fields() {
+        let mut message = MrpData::from(&parse_mrp_data(&TEST_PAYLOAD).unwrap());
+        if let Some(Body::Test(test)) = &mut message.tlvs[0].body {
+            test.sa.pop();
+        }
+        assert!(MRPData::from_proto_bytes(&message.encode_to_vec()).is_err());
+
+        let message = MrpData {
+            version: 0x1_0000,
+            tlvs: Vec::new(),
+        };
+        assert!(MRPData::from_proto_bytes(&message.encode_to_vec()).is_err());
+    }
+}
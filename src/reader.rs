@@ -0,0 +1,152 @@
+use crate::borrowed::MrpTlvIter;
+use crate::{MRPParseError, MRPTLVHeader, UnknownTlvPolicy};
+
+/// Decodes the TLVs of a possibly incomplete PDU, for buffers that are still filling up.
+///
+/// Every TLV that fits in the buffer is yielded in order. If the buffer ends before MRP_End,
+/// the last item is [`MRPParseError::NeedMoreData`]; `missing` is exact when a header or value
+/// is cut short and 2 (the next TLV header) when the buffer ends on a TLV boundary. Call
+/// [`offset`](TlvReader::offset) to see how far decoding got before the error.
+///
+/// The TLVs are walked by the same code as [`crate::parse_mrp_data`]; only truncation is
+/// reported differently.
+#[derive(Debug, Clone)]
+pub struct TlvReader<'a> {
+    data: &'a [u8],
+    tlvs: MrpTlvIter<'a>,
+    policy: UnknownTlvPolicy,
+    offset: usize,
+    done: bool,
+}
+
+impl<'a> TlvReader<'a> {
+    /// `data` starts with the MRP version, as passed to [`crate::parse_mrp_data`].
+    pub fn new(data: &'a [u8]) -> Self {
+        TlvReader::with_policy(data, UnknownTlvPolicy::Keep)
+    }
+
+    pub fn with_policy(data: &'a [u8], policy: UnknownTlvPolicy) -> Self {
+        TlvReader {
+            data,
+            tlvs: MrpTlvIter::new(data, 2),
+            policy,
+            offset: 2,
+            done: false,
+        }
+    }
+
+    /// Offset in `data` of the first TLV that has not been yielded yet.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn need_more(&mut self, missing: usize) -> Option<Result<MRPTLVHeader, MRPParseError>> {
+        self.done = true;
+        Some(Err(MRPParseError::NeedMoreData { missing }))
+    }
+}
+
+impl<'a> Iterator for TlvReader<'a> {
+    type Item = Result<MRPTLVHeader, MRPParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        if self.data.len() < 2 {
+            return self.need_more(2 - self.data.len());
+        }
+        match self.tlvs.next() {
+            None => self.need_more(2),
+            Some(Err(MRPParseError::TruncatedHeader { offset })) => {
+                self.need_more(offset + 2 - self.data.len())
+            }
+            Some(Err(MRPParseError::TruncatedTlvValue {
+                expected,
+                available,
+                ..
+            })) => self.need_more(expected - available),
+            Some(Err(err)) => {
+                self.done = true;
+                Some(Err(err))
+            }
+            Some(Ok(tlv)) => {
+                self.offset += 2 + tlv.value.len();
+                self.done = tlv.tlv_type == 0x00;
+                let header = tlv.to_owned_with_policy(self.policy);
+                if header.is_err() {
+                    self.done = true;
+                }
+                Some(header)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_mrp_data;
+    use crate::TEST_PAYLOAD;
+    use alloc::vec::Vec;
+
+    /// Offsets at which each TLV of `TEST_PAYLOAD` ends.
+    const TLV_ENDS: [usize; 4] = [22, 42, 50, 52];
+
+    #[test]
+    fn test_complete_payload_matches_parse() {
+        let headers: Vec<MRPTLVHeader> =
+            TlvReader::new(&TEST_PAYLOAD).map(Result::unwrap).collect();
+        assert_eq!(headers, parse_mrp_data(&TEST_PAYLOAD).unwrap().tlv_headers);
+    }
+
+    #[test]
+    fn test_every_prefix() {
+        let all = parse_mrp_data(&TEST_PAYLOAD).unwrap().tlv_headers;
+        for len in 0..TEST_PAYLOAD.len() {
+            let mut reader = TlvReader::new(&TEST_PAYLOAD[..len]);
+            let complete = TLV_ENDS.iter().filter(|end| **end <= len).count();
+            for expected in &all[..complete] {
+                assert_eq!(&reader.next().unwrap().unwrap(), expected, "len {}", len);
+            }
+
+            // The TLV being filled starts where the previous one ended.
+            let start = if complete == 0 {
+                2
+            } else {
+                TLV_ENDS[complete - 1]
+            };
+            let missing = if len < 2 {
+                2 - len
+            } else if len < start + 2 {
+                start + 2 - len
+            } else {
+                TLV_ENDS[complete] - len
+            };
+            assert_eq!(
+                reader.next(),
+                Some(Err(MRPParseError::NeedMoreData { missing })),
+                "len {}",
+                len
+            );
+            assert_eq!(reader.next(), None);
+            assert_eq!(reader.offset(), start);
+        }
+    }
+
+    #[test]
+    fn test_decode_errors_pass_through() {
+        // A Test TLV that is complete on the wire but too short for its type.
+        let payload = [0x00, 0x01, 0x02, 0x02, 0xa0, 0x00];
+        let mut reader = TlvReader::new(&payload);
+        assert_eq!(
+            reader.next(),
+            Some(Err(MRPParseError::InvalidTlvLength {
+                tlv_type: 0x02,
+                expected: 18,
+                actual: 2,
+            }))
+        );
+        assert_eq!(reader.next(), None);
+    }
+}
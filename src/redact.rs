@@ -0,0 +1,272 @@
+use crate::{MRPData, MRPSubTlv, MRPTLVData, MacAddress};
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use uuid::Uuid;
+
+/// Real-to-pseudonym pairs recorded by a [`Redactor`], for de-anonymizing findings internally.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MappingTable {
+    pub macs: Vec<(MacAddress, MacAddress)>,
+    pub uuids: Vec<(Uuid, Uuid)>,
+}
+
+impl MappingTable {
+    pub fn restore_mac(&self, pseudonym: &MacAddress) -> Option<MacAddress> {
+        self.macs
+            .iter()
+            .find(|(_, redacted)| redacted == pseudonym)
+            .map(|(real, _)| *real)
+    }
+
+    pub fn restore_uuid(&self, pseudonym: &Uuid) -> Option<Uuid> {
+        self.uuids
+            .iter()
+            .find(|(_, redacted)| redacted == pseudonym)
+            .map(|(real, _)| *real)
+    }
+}
+
+/// Maps MAC addresses and domain UUIDs to stable pseudonyms.
+///
+/// Pseudonyms are derived from a seeded hash, so the same seed yields the same mapping
+/// across runs. Redacted MACs keep their individual/group bit and have the
+/// locally-administered bit set; redacted UUIDs are random-looking version 4 UUIDs.
+///
+/// [`MRPData::display_redacted`] and, with the `json` feature,
+/// `MRPData::to_json_string_redacted` redact as they format. The other outputs, such as
+/// [`MRPData::summary`], MessagePack and protobuf, have no redacting variant: format the copy
+/// returned by [`redacted`](Redactor::redacted) instead.
+#[derive(Debug)]
+pub struct Redactor {
+    seed: u64,
+    macs: BTreeMap<[u8; 6], [u8; 6]>,
+    mac_pseudonyms: BTreeMap<[u8; 6], [u8; 6]>,
+    uuids: BTreeMap<Uuid, Uuid>,
+    uuid_pseudonyms: BTreeMap<Uuid, Uuid>,
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+impl Redactor {
+    pub fn new(seed: u64) -> Self {
+        Redactor {
+            seed,
+            macs: BTreeMap::new(),
+            mac_pseudonyms: BTreeMap::new(),
+            uuids: BTreeMap::new(),
+            uuid_pseudonyms: BTreeMap::new(),
+        }
+    }
+
+    fn hash(&self, bytes: &[u8], round: u64) -> u64 {
+        splitmix64(self.seed ^ fnv1a(bytes) ^ splitmix64(round))
+    }
+
+    pub fn redact_mac(&mut self, mac: &MacAddress) -> MacAddress {
+        if let Some(pseudonym) = self.macs.get(&mac.0) {
+            return MacAddress(*pseudonym);
+        }
+        let mut round = 0;
+        let pseudonym = loop {
+            let hash = self.hash(&mac.0, round).to_be_bytes();
+            let mut candidate = [hash[0], hash[1], hash[2], hash[3], hash[4], hash[5]];
+            candidate[0] = (candidate[0] & 0xfc) | 0x02 | (mac.0[0] & 0x01);
+            if !self.mac_pseudonyms.contains_key(&candidate) {
+                break candidate;
+            }
+            round += 1;
+        };
+        self.macs.insert(mac.0, pseudonym);
+        self.mac_pseudonyms.insert(pseudonym, mac.0);
+        MacAddress(pseudonym)
+    }
+
+    pub fn redact_uuid(&mut self, uuid: &Uuid) -> Uuid {
+        if let Some(pseudonym) = self.uuids.get(uuid) {
+            return *pseudonym;
+        }
+        let mut round = 0;
+        let pseudonym = loop {
+            let mut bytes = [0u8; 16];
+            bytes[..8].copy_from_slice(&self.hash(uuid.as_bytes(), round).to_be_bytes());
+            bytes[8..].copy_from_slice(&self.hash(uuid.as_bytes(), round + 1).to_be_bytes());
+            let candidate = uuid::Builder::from_random_bytes(bytes).into_uuid();
+            if !self.uuid_pseudonyms.contains_key(&candidate) {
+                break candidate;
+            }
+            round += 2;
+        };
+        self.uuids.insert(*uuid, pseudonym);
+        self.uuid_pseudonyms.insert(pseudonym, *uuid);
+        pseudonym
+    }
+
+    /// Replaces every SA (including those in MRP_Option sub-TLVs) and domain UUID in `mrp_data` with its pseudonym.
+    ///
+    /// This rewrites the caller's PDU in place. To keep the original and only redact what is
+    /// displayed or exported, use [`redacted`](Redactor::redacted).
+    pub fn redact(&mut self, mrp_data: &mut MRPData) {
+        for header in mrp_data.iter_mut() {
+            match &mut header.data {
+                MRPTLVData::MRPTest(data) => data.sa = self.redact_mac(&data.sa),
+                MRPTLVData::MRPTopologyChange(data) => data.sa = self.redact_mac(&data.sa),
+                MRPTLVData::MRPLinkDown(data) | MRPTLVData::MRPLinkUp(data) => {
+                    data.sa = self.redact_mac(&data.sa)
+                }
+                MRPTLVData::MRPInTest(data) => data.sa = self.redact_mac(&data.sa),
+                MRPTLVData::MRPInTopologyChange(data) => data.sa = self.redact_mac(&data.sa),
+                MRPTLVData::MRPInLinkDown(data) | MRPTLVData::MRPInLinkUp(data) => {
+                    data.sa = self.redact_mac(&data.sa)
+                }
+                MRPTLVData::MRPInLinkStatusPoll(data) => data.sa = self.redact_mac(&data.sa),
+                MRPTLVData::MRPCommon(data) => {
+                    data.domain_uuid = self.redact_uuid(&data.domain_uuid)
+                }
+                MRPTLVData::MRPOption(data) => {
+                    for sub_tlv in &mut data.sub_tlvs {
+                        if let MRPSubTlv::TestMgrNAck(manager) | MRPSubTlv::TestPropagate(manager) =
+                            sub_tlv
+                        {
+                            manager.sa = self.redact_mac(&manager.sa);
+                            manager.other_sa = self.redact_mac(&manager.other_sa);
+                        }
+                    }
+                }
+                MRPTLVData::MRPEnd | MRPTLVData::Unknown { .. } => {}
+            }
+        }
+    }
+
+    /// A redacted copy of `mrp_data` for [`Display`](core::fmt::Display) or any exporter; the
+    /// original is left untouched.
+    pub fn redacted(&mut self, mrp_data: &MRPData) -> MRPData {
+        let mut copy = mrp_data.clone();
+        self.redact(&mut copy);
+        copy
+    }
+
+    pub fn mapping_table(&self) -> MappingTable {
+        MappingTable {
+            macs: self
+                .macs
+                .iter()
+                .map(|(real, redacted)| (MacAddress(*real), MacAddress(*redacted)))
+                .collect(),
+            uuids: self
+                .uuids
+                .iter()
+                .map(|(real, redacted)| (*real, *redacted))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_mrp_data;
+    use crate::TEST_PAYLOAD;
+
+    fn sample_mac(n: u8) -> MacAddress {
+        MacAddress([0x00, 0x0e, 0x8c, 0x00, n / 16, n])
+    }
+
+    #[test]
+    fn test_redaction_is_deterministic() {
+        let mut first = Redactor::new(42);
+        let mut second = Redactor::new(42);
+        let mac = sample_mac(1);
+        assert_eq!(first.redact_mac(&mac), second.redact_mac(&mac));
+        assert_eq!(first.redact_mac(&mac), first.redact_mac(&mac));
+        assert_ne!(Redactor::new(7).redact_mac(&mac), first.redact_mac(&mac));
+
+        let mut first_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        let mut second_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        first.redact(&mut first_data);
+        second.redact(&mut second_data);
+        assert_eq!(first_data, second_data);
+        assert_ne!(first_data, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+    }
+
+    #[test]
+    fn test_redaction_preserves_format() {
+        let mut redactor = Redactor::new(0);
+        let redacted = redactor.redact_mac(&sample_mac(3));
+        assert_eq!(redacted.0[0] & 0x02, 0x02);
+        assert_eq!(redacted.0[0] & 0x01, 0x00);
+        let group = redactor.redact_mac(&MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x01]));
+        assert_eq!(group.0[0] & 0x03, 0x03);
+
+        let uuid = Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap();
+        let redacted = redactor.redact_uuid(&uuid);
+        assert_eq!(redacted.get_version_num(), 4);
+        assert_eq!(redacted.get_variant(), uuid::Variant::RFC4122);
+    }
+
+    #[test]
+    fn test_mapping_table_round_trip() {
+        let mut redactor = Redactor::new(99);
+        let mut mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        redactor.redact(&mut mrp_data);
+        let table = redactor.mapping_table();
+        assert_eq!(table.macs.len(), 1);
+        assert_eq!(table.uuids.len(), 1);
+
+        let MRPTLVData::MRPTest(test) = &mrp_data.tlv_headers[0].data else {
+            panic!("Expected MRPTest data");
+        };
+        assert_eq!(
+            table.restore_mac(&test.sa).unwrap().to_string(),
+            "00:0e:8c:e0:2f:22"
+        );
+        let MRPTLVData::MRPCommon(common) = &mrp_data.tlv_headers[1].data else {
+            panic!("Expected MRPCommon data");
+        };
+        assert_eq!(
+            table.restore_uuid(&common.domain_uuid),
+            Some(Uuid::parse_str("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_redacted_copy() {
+        let original = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        let mut redactor = Redactor::new(5);
+        let copy = redactor.redacted(&original);
+        assert_eq!(original, parse_mrp_data(&TEST_PAYLOAD).unwrap());
+
+        let mut in_place = original.clone();
+        Redactor::new(5).redact(&mut in_place);
+        assert_eq!(copy, in_place);
+        let text = copy.to_string();
+        assert!(!text.contains("00:0e:8c:e0:2f:22"));
+        assert!(!text.contains("c3d687fe-789e-03a1-acdb-e5bfcbbc27b6"));
+        assert!(text.contains(
+            &redactor
+                .redact_mac(&MacAddress([0x00, 0x0e, 0x8c, 0xe0, 0x2f, 0x22]))
+                .to_string()
+        ));
+    }
+
+    #[test]
+    fn test_no_collisions() {
+        let mut redactor = Redactor::new(1);
+        let mut pseudonyms = BTreeMap::new();
+        for n in 0..=255 {
+            pseudonyms.insert(redactor.redact_mac(&sample_mac(n)).0, n);
+        }
+        assert_eq!(pseudonyms.len(), 256);
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{
+    parse_tlv_data, MRPCommonData, MRPInLinkData, MRPInLinkStatusPollData, MRPInTestData,
+    MRPInTopologyChangeData, MRPLinkData, MRPOptionData, MRPTLVData, MRPTestData,
+    MRPTopologyChangeData, MacAddress, UnknownTlvPolicy,
+};
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes as "aa:bb:cc:dd:ee:ff" in human-readable formats and as 6 raw bytes otherwise.
+impl Serialize for MacAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MacAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = <Cow<'de, str>>::deserialize(deserializer)?;
+            text.parse().map_err(D::Error::custom)
+        } else {
+            <[u8; 6]>::deserialize(deserializer).map(MacAddress)
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum TaggedTlvRef<'a> {
+    Test(&'a MRPTestData),
+    Common(&'a MRPCommonData),
+    TopologyChange(&'a MRPTopologyChangeData),
+    LinkDown(&'a MRPLinkData),
+    LinkUp(&'a MRPLinkData),
+    InTest(&'a MRPInTestData),
+    InTopologyChange(&'a MRPInTopologyChangeData),
+    InLinkDown(&'a MRPInLinkData),
+    InLinkUp(&'a MRPInLinkData),
+    InLinkStatusPoll(&'a MRPInLinkStatusPollData),
+    Option(&'a MRPOptionData),
+    End,
+    Unknown { tlv_type: u8, raw: &'a [u8] },
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "type", content = "data", rename_all = "snake_case")]
+enum TaggedTlv {
+    Test(MRPTestData),
+    Common(MRPCommonData),
+    TopologyChange(MRPTopologyChangeData),
+    LinkDown(MRPLinkData),
+    LinkUp(MRPLinkData),
+    InTest(MRPInTestData),
+    InTopologyChange(MRPInTopologyChangeData),
+    InLinkDown(MRPInLinkData),
+    InLinkUp(MRPInLinkData),
+    InLinkStatusPoll(MRPInLinkStatusPollData),
+    Option(MRPOptionData),
+    End,
+    Unknown { tlv_type: u8, raw: Vec<u8> },
+}
+
+impl Serialize for MRPTLVData {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            let mut payload = Vec::with_capacity(self.payload_length());
+            self.write_payload(&mut payload);
+            return (self.type_byte(), payload).serialize(serializer);
+        }
+        match self {
+            MRPTLVData::MRPTest(data) => TaggedTlvRef::Test(data),
+            MRPTLVData::MRPCommon(data) => TaggedTlvRef::Common(data),
+            MRPTLVData::MRPTopologyChange(data) => TaggedTlvRef::TopologyChange(data),
+            MRPTLVData::MRPLinkDown(data) => TaggedTlvRef::LinkDown(data),
+            MRPTLVData::MRPLinkUp(data) => TaggedTlvRef::LinkUp(data),
+            MRPTLVData::MRPInTest(data) => TaggedTlvRef::InTest(data),
+            MRPTLVData::MRPInTopologyChange(data) => TaggedTlvRef::InTopologyChange(data),
+            MRPTLVData::MRPInLinkDown(data) => TaggedTlvRef::InLinkDown(data),
+            MRPTLVData::MRPInLinkUp(data) => TaggedTlvRef::InLinkUp(data),
+            MRPTLVData::MRPInLinkStatusPoll(data) => TaggedTlvRef::InLinkStatusPoll(data),
+            MRPTLVData::MRPOption(data) => TaggedTlvRef::Option(data),
+            MRPTLVData::MRPEnd => TaggedTlvRef::End,
+            MRPTLVData::Unknown { tlv_type, raw } => TaggedTlvRef::Unknown {
+                tlv_type: *tlv_type,
+                raw,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MRPTLVData {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            let (tlv_type, payload) = <(u8, Vec<u8>)>::deserialize(deserializer)?;
+            return parse_tlv_data(tlv_type, &payload, UnknownTlvPolicy::Keep)
+                .map_err(D::Error::custom);
+        }
+        Ok(match TaggedTlv::deserialize(deserializer)? {
+            TaggedTlv::Test(data) => MRPTLVData::MRPTest(data),
+            TaggedTlv::Common(data) => MRPTLVData::MRPCommon(data),
+            TaggedTlv::TopologyChange(data) => MRPTLVData::MRPTopologyChange(data),
+            TaggedTlv::LinkDown(data) => MRPTLVData::MRPLinkDown(data),
+            TaggedTlv::LinkUp(data) => MRPTLVData::MRPLinkUp(data),
+            TaggedTlv::InTest(data) => MRPTLVData::MRPInTest(data),
+            TaggedTlv::InTopologyChange(data) => MRPTLVData::MRPInTopologyChange(data),
+            TaggedTlv::InLinkDown(data) => MRPTLVData::MRPInLinkDown(data),
+            TaggedTlv::InLinkUp(data) => MRPTLVData::MRPInLinkUp(data),
+            TaggedTlv::InLinkStatusPoll(data) => MRPTLVData::MRPInLinkStatusPoll(data),
+            TaggedTlv::Option(data) => MRPTLVData::MRPOption(data),
+            TaggedTlv::End => MRPTLVData::MRPEnd,
+            TaggedTlv::Unknown { tlv_type, raw } => MRPTLVData::Unknown { tlv_type, raw },
+        })
+    }
+}
@@ -0,0 +1,298 @@
+use crate::validate::ValidationIssue;
+use crate::{MRPData, MRPTLVData, MacAddress};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Smallest Ethernet payload; shorter PDUs are zero-padded by the sender.
+pub const ETHERNET_MIN_PAYLOAD: usize = 46;
+/// Largest Ethernet payload an MRP PDU can occupy.
+pub const ETHERNET_MAX_PAYLOAD: usize = 1500;
+
+/// Destination group for MRP_Test frames.
+pub const MC_TEST: MacAddress = MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x01]);
+/// Destination group for MRP_TopologyChange, MRP_LinkDown and MRP_LinkUp frames.
+pub const MC_CONTROL: MacAddress = MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x02]);
+/// Destination group for MRP_InTest frames.
+pub const MC_INTEST: MacAddress = MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x03]);
+/// Destination group for MRP_InTopologyChange, MRP_InLinkDown/Up and MRP_InLinkStatusPoll frames.
+pub const MC_INCONTROL: MacAddress = MacAddress([0x01, 0x15, 0x4e, 0x00, 0x00, 0x04]);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The PDU does not start with a Test, TopologyChange, LinkDown, LinkUp or interconnection TLV.
+    MissingPduTlv,
+    /// More than one Test/TopologyChange/Link (or interconnection) TLV is present.
+    MultiplePduTlvs,
+    MissingCommon,
+    MissingEnd,
+    /// An End TLV is present but other TLVs follow it.
+    EndNotLast,
+    PayloadTooLong {
+        len: usize,
+        max: usize,
+    },
+    /// A TLV value is too long for its length byte, so the PDU cannot be encoded.
+    ValueTooLong {
+        tlv_type: u8,
+        length: usize,
+    },
+    /// An MRP_Option breaks the Ed1 rule, so the PDU cannot be encoded.
+    MisplacedEd1 {
+        index: usize,
+    },
+    /// The frame is addressed to a different group than [`suggested_destination`] gives.
+    WrongDestination {
+        expected: MacAddress,
+        got: MacAddress,
+    },
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::MissingPduTlv => write!(f, "PDU does not start with an MRP frame TLV"),
+            Violation::MultiplePduTlvs => write!(f, "PDU carries more than one MRP frame TLV"),
+            Violation::MissingCommon => write!(f, "PDU has no MRP_Common TLV"),
+            Violation::MissingEnd => write!(f, "PDU has no MRP_End TLV"),
+            Violation::EndNotLast => write!(f, "MRP_End TLV is not the last TLV"),
+            Violation::PayloadTooLong { len, max } => {
+                write!(f, "PDU is {} bytes, at most {} fit in a frame", len, max)
+            }
+            Violation::ValueTooLong { tlv_type, length } => write!(
+                f,
+                "TLV type {:#04x} value is {} bytes, at most 255 can be encoded",
+                tlv_type, length
+            ),
+            Violation::MisplacedEd1 { index } => write!(
+                f,
+                "MRP_Option sub-TLV {} breaks the rule that Ed1 leads a Siemens option only",
+                index
+            ),
+            Violation::WrongDestination { expected, got } => {
+                write!(f, "PDU is addressed to {}, expected {}", got, expected)
+            }
+        }
+    }
+}
+
+/// Whether the send paths run [`check_for_transmit`] before putting a frame on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TransmitCheck {
+    #[default]
+    Enforce,
+    /// Send the PDU as-is, for deliberately malformed robustness testing.
+    Skip,
+}
+
+/// Encoded size of the PDU (version plus every TLV header and value).
+///
+/// Sizes come from the TLV data, as [`MRPData::to_bytes`] writes them, not from the stored
+/// length bytes.
+pub fn encoded_len(mrp_data: &MRPData) -> usize {
+    2 + mrp_data
+        .tlv_headers
+        .iter()
+        .map(|header| 2 + header.data.payload_length())
+        .sum::<usize>()
+}
+
+/// Size of the Ethernet payload once the PDU has been padded to the frame minimum.
+///
+/// Senders zero-pad short PDUs, so only [`ETHERNET_MAX_PAYLOAD`] can be exceeded.
+pub fn padded_len(mrp_data: &MRPData) -> usize {
+    encoded_len(mrp_data).max(ETHERNET_MIN_PAYLOAD)
+}
+
+/// The MRP multicast group the PDU should be sent to, if it carries a frame TLV.
+pub fn suggested_destination(mrp_data: &MRPData) -> Option<MacAddress> {
+    match mrp_data.tlv_headers.first().map(|header| &header.data) {
+        Some(MRPTLVData::MRPTest(_)) => Some(MC_TEST),
+        Some(MRPTLVData::MRPTopologyChange(_))
+        | Some(MRPTLVData::MRPLinkDown(_))
+        | Some(MRPTLVData::MRPLinkUp(_)) => Some(MC_CONTROL),
+        Some(MRPTLVData::MRPInTest(_)) => Some(MC_INTEST),
+        Some(MRPTLVData::MRPInTopologyChange(_))
+        | Some(MRPTLVData::MRPInLinkDown(_))
+        | Some(MRPTLVData::MRPInLinkUp(_))
+        | Some(MRPTLVData::MRPInLinkStatusPoll(_)) => Some(MC_INCONTROL),
+        _ => None,
+    }
+}
+
+/// Runs [`MRPData::validate`] and keeps the issues that stop a PDU from being sent, plus the
+/// maximum frame size check.
+pub fn pre_transmit_check(mrp_data: &MRPData) -> Result<(), Vec<Violation>> {
+    let mut violations: Vec<Violation> = mrp_data
+        .validate()
+        .into_iter()
+        .filter_map(|issue| match issue {
+            ValidationIssue::MissingFrameTlv => Some(Violation::MissingPduTlv),
+            ValidationIssue::MultipleFrameTlvs => Some(Violation::MultiplePduTlvs),
+            ValidationIssue::MissingCommon => Some(Violation::MissingCommon),
+            ValidationIssue::MissingEndTlv => Some(Violation::MissingEnd),
+            ValidationIssue::EndNotLast => Some(Violation::EndNotLast),
+            ValidationIssue::ValueTooLong { tlv_type, length } => {
+                Some(Violation::ValueTooLong { tlv_type, length })
+            }
+            ValidationIssue::MisplacedEd1 { index } => Some(Violation::MisplacedEd1 { index }),
+            _ => None,
+        })
+        .collect();
+    let len = padded_len(mrp_data);
+    if len > ETHERNET_MAX_PAYLOAD {
+        violations.push(Violation::PayloadTooLong {
+            len,
+            max: ETHERNET_MAX_PAYLOAD,
+        });
+    }
+
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Entry point for send paths: unless `check` opts out, runs [`pre_transmit_check`] and
+/// checks that `dst` is the [`suggested_destination`] for the PDU.
+pub fn check_for_transmit(
+    mrp_data: &MRPData,
+    dst: MacAddress,
+    check: TransmitCheck,
+) -> Result<(), Vec<Violation>> {
+    if check == TransmitCheck::Skip {
+        return Ok(());
+    }
+    let mut violations = pre_transmit_check(mrp_data).err().unwrap_or_default();
+    if let Some(expected) = suggested_destination(mrp_data).filter(|expected| *expected != dst) {
+        violations.push(Violation::WrongDestination { expected, got: dst });
+    }
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_mrp_data, MRPOptionData, MRPSubTlv};
+
+    const LINK_DOWN_PAYLOAD: [u8; 38] = [
+        0x00, 0x01, 0x04, 0x0c, 0x00, 0x1b, 0x1b, 0x6c, 0x52, 0x21, 0x00, 0x01, 0x00, 0x14, 0x00,
+        0x01, 0x01, 0x12, 0x00, 0x2f, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb,
+        0xe5, 0xbf, 0xcb, 0xbc, 0x27, 0xb6, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn test_valid_link_down() {
+        let mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        assert_eq!(pre_transmit_check(&mrp_data), Ok(()));
+        assert_eq!(suggested_destination(&mrp_data), Some(MC_CONTROL));
+        assert_eq!(encoded_len(&mrp_data), 38);
+        assert_eq!(padded_len(&mrp_data), ETHERNET_MIN_PAYLOAD);
+    }
+
+    #[test]
+    fn test_link_down_missing_common() {
+        let mut mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        mrp_data.tlv_headers.remove(1);
+        assert_eq!(
+            pre_transmit_check(&mrp_data),
+            Err(vec![Violation::MissingCommon])
+        );
+    }
+
+    #[test]
+    fn test_stale_length_bytes() {
+        let mut mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        mrp_data.tlv_headers[0].length = 0;
+        assert_eq!(encoded_len(&mrp_data), mrp_data.to_bytes().unwrap().len());
+
+        // Six full Option TLVs whose length bytes claim to be empty.
+        let end = mrp_data.tlv_headers.pop().unwrap();
+        for _ in 0..6 {
+            let option = MRPOptionData::new(
+                [0x00, 0x80, 0x63],
+                vec![MRPSubTlv::Raw {
+                    sub_type: 0x80,
+                    value: vec![0; 250],
+                }],
+            );
+            mrp_data.push(MRPTLVData::MRPOption(option)).unwrap();
+            mrp_data.tlv_headers.last_mut().unwrap().length = 0;
+        }
+        mrp_data.tlv_headers.push(end);
+        let len = mrp_data.to_bytes().unwrap().len();
+        assert_eq!(len, 38 + 6 * 257);
+        assert_eq!(
+            pre_transmit_check(&mrp_data),
+            Err(vec![Violation::PayloadTooLong {
+                len,
+                max: ETHERNET_MAX_PAYLOAD,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_value_too_long() {
+        let mut mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        let mut option = mrp_data.tlv_headers[2].clone();
+        option.tlv_type = 0x7f;
+        option.data = MRPTLVData::MRPOption(MRPOptionData::new(
+            [0x00, 0x80, 0x63],
+            vec![MRPSubTlv::Raw {
+                sub_type: 0x80,
+                value: vec![0; 251],
+            }],
+        ));
+        mrp_data.tlv_headers.insert(2, option);
+        assert_eq!(
+            pre_transmit_check(&mrp_data),
+            Err(vec![Violation::ValueTooLong {
+                tlv_type: 0x7f,
+                length: 256,
+            }])
+        );
+    }
+
+    #[test]
+    fn test_skip_check() {
+        let mut mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        mrp_data.tlv_headers.truncate(1);
+        assert_eq!(
+            check_for_transmit(&mrp_data, MC_CONTROL, TransmitCheck::Enforce),
+            Err(vec![Violation::MissingCommon, Violation::MissingEnd])
+        );
+        assert_eq!(
+            check_for_transmit(&mrp_data, MC_CONTROL, TransmitCheck::Skip),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_wrong_destination() {
+        let mrp_data = parse_mrp_data(&LINK_DOWN_PAYLOAD).unwrap();
+        assert_eq!(
+            check_for_transmit(&mrp_data, MC_CONTROL, TransmitCheck::Enforce),
+            Ok(())
+        );
+        let violation = Violation::WrongDestination {
+            expected: MC_CONTROL,
+            got: MC_TEST,
+        };
+        assert_eq!(
+            check_for_transmit(&mrp_data, MC_TEST, TransmitCheck::Enforce),
+            Err(vec![violation.clone()])
+        );
+        assert_eq!(
+            violation.to_string(),
+            "PDU is addressed to 01:15:4e:00:00:01, expected 01:15:4e:00:00:02"
+        );
+        assert_eq!(
+            check_for_transmit(&mrp_data, MC_TEST, TransmitCheck::Skip),
+            Ok(())
+        );
+    }
+}
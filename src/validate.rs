@@ -0,0 +1,372 @@
+use crate::{MRPData, MRPTLVData, MRP_VERSION};
+use alloc::vec::Vec;
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Unusual but interoperable, e.g. a TLV type this crate does not decode.
+    Warning,
+    /// Violates IEC 62439-2; conforming devices may drop the frame.
+    Error,
+}
+
+/// A conformance problem found by [`MRPData::validate`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ValidationIssue {
+    UnsupportedVersion(u16),
+    /// The PDU does not start with a Test, TopologyChange, LinkDown/Up or interconnection TLV.
+    MissingFrameTlv,
+    /// More than one Test/TopologyChange/Link (or interconnection) TLV is present.
+    MultipleFrameTlvs,
+    MissingCommon,
+    DuplicateCommon,
+    MissingEndTlv,
+    /// An End TLV is present but other TLVs follow it.
+    EndNotLast,
+    /// A TLV's length byte does not match the size of the value its type carries.
+    WrongTlvLength {
+        tlv_type: u8,
+        expected: usize,
+        actual: usize,
+    },
+    /// A TLV value is `length` bytes, more than a one-byte length field can describe.
+    ValueTooLong {
+        tlv_type: u8,
+        length: usize,
+    },
+    /// MRP_Option sub-TLV `index` breaks the Ed1 rule; see
+    /// [`MRPOptionData::misplaced_ed1`](crate::MRPOptionData::misplaced_ed1).
+    MisplacedEd1 {
+        index: usize,
+    },
+    /// A TLV's type byte does not match the data it carries.
+    TlvTypeMismatch {
+        tlv_type: u8,
+        expected: u8,
+    },
+    UnknownTlvType(u8),
+}
+
+impl ValidationIssue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            ValidationIssue::UnsupportedVersion(_) | ValidationIssue::UnknownTlvType(_) => {
+                Severity::Warning
+            }
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationIssue::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "MRP version {:#06x} is not {:#06x}",
+                    version, MRP_VERSION
+                )
+            }
+            ValidationIssue::MissingFrameTlv => {
+                write!(f, "PDU does not start with an MRP frame TLV")
+            }
+            ValidationIssue::MultipleFrameTlvs => {
+                write!(f, "PDU carries more than one MRP frame TLV")
+            }
+            ValidationIssue::MissingCommon => write!(f, "PDU has no MRP_Common TLV"),
+            ValidationIssue::DuplicateCommon => write!(f, "PDU has more than one MRP_Common TLV"),
+            ValidationIssue::MissingEndTlv => write!(f, "PDU has no MRP_End TLV"),
+            ValidationIssue::EndNotLast => write!(f, "MRP_End TLV is not the last TLV"),
+            ValidationIssue::WrongTlvLength {
+                tlv_type,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "TLV type {:#04x} has length {}, expected {}",
+                tlv_type, actual, expected
+            ),
+            ValidationIssue::ValueTooLong { tlv_type, length } => write!(
+                f,
+                "TLV type {:#04x} value is {} bytes, at most 255 can be encoded",
+                tlv_type, length
+            ),
+            ValidationIssue::MisplacedEd1 { index } => write!(
+                f,
+                "MRP_Option sub-TLV {} breaks the rule that Ed1 leads a Siemens option only",
+                index
+            ),
+            ValidationIssue::TlvTypeMismatch { tlv_type, expected } => write!(
+                f,
+                "TLV type byte {:#04x} does not match its data, expected {:#04x}",
+                tlv_type, expected
+            ),
+            ValidationIssue::UnknownTlvType(tlv_type) => {
+                write!(
+                    f,
+                    "TLV type {:#04x} is not defined by IEC 62439-2",
+                    tlv_type
+                )
+            }
+        }
+    }
+}
+
+/// Whether `data` is the TLV that gives an MRP frame its kind.
+pub(crate) fn is_frame_tlv(data: &MRPTLVData) -> bool {
+    matches!(
+        data,
+        MRPTLVData::MRPTest(_)
+            | MRPTLVData::MRPTopologyChange(_)
+            | MRPTLVData::MRPLinkDown(_)
+            | MRPTLVData::MRPLinkUp(_)
+            | MRPTLVData::MRPInTest(_)
+            | MRPTLVData::MRPInTopologyChange(_)
+            | MRPTLVData::MRPInLinkDown(_)
+            | MRPTLVData::MRPInLinkUp(_)
+            | MRPTLVData::MRPInLinkStatusPoll(_)
+    )
+}
+
+impl MRPData {
+    /// Checks the parsed PDU against IEC 62439-2.
+    ///
+    /// The parser accepts anything it can decode; this is the opt-in strict pass. Frame-level
+    /// issues come first, followed by per-TLV issues in TLV order.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let headers = &self.tlv_headers;
+
+        if self.version != MRP_VERSION {
+            issues.push(ValidationIssue::UnsupportedVersion(self.version));
+        }
+        if !headers
+            .first()
+            .is_some_and(|header| is_frame_tlv(&header.data))
+        {
+            issues.push(ValidationIssue::MissingFrameTlv);
+        }
+        if headers
+            .iter()
+            .filter(|header| is_frame_tlv(&header.data))
+            .count()
+            > 1
+        {
+            issues.push(ValidationIssue::MultipleFrameTlvs);
+        }
+        match self.commons().count() {
+            0 => issues.push(ValidationIssue::MissingCommon),
+            1 => {}
+            _ => issues.push(ValidationIssue::DuplicateCommon),
+        }
+        match headers
+            .iter()
+            .position(|header| matches!(header.data, MRPTLVData::MRPEnd))
+        {
+            None => issues.push(ValidationIssue::MissingEndTlv),
+            Some(index) if index + 1 != headers.len() => issues.push(ValidationIssue::EndNotLast),
+            Some(_) => {}
+        }
+
+        for header in headers {
+            if let MRPTLVData::Unknown { tlv_type, .. } = header.data {
+                issues.push(ValidationIssue::UnknownTlvType(tlv_type));
+            }
+            if let MRPTLVData::MRPOption(option) = &header.data {
+                if let Some(index) = option.misplaced_ed1() {
+                    issues.push(ValidationIssue::MisplacedEd1 { index });
+                }
+            }
+            let expected_type = header.data.type_byte();
+            if header.tlv_type != expected_type {
+                issues.push(ValidationIssue::TlvTypeMismatch {
+                    tlv_type: header.tlv_type,
+                    expected: expected_type,
+                });
+            }
+            let expected_length = header.data.payload_length();
+            if expected_length > usize::from(u8::MAX) {
+                issues.push(ValidationIssue::ValueTooLong {
+                    tlv_type: header.tlv_type,
+                    length: expected_length,
+                });
+            } else if header.length as usize != expected_length {
+                issues.push(ValidationIssue::WrongTlvLength {
+                    tlv_type: header.tlv_type,
+                    expected: expected_length,
+                    actual: header.length as usize,
+                });
+            }
+        }
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TEST_PAYLOAD;
+    use crate::{parse_mrp_data, MRPOptionData, MRPSubTlv};
+
+    const COMMON: [u8; 20] = [
+        0x01, 0x12, 0x05, 0x7e, 0xc3, 0xd6, 0x87, 0xfe, 0x78, 0x9e, 0x03, 0xa1, 0xac, 0xdb, 0xe5,
+        0xbf, 0xcb, 0xbc, 0x27, 0xb6,
+    ];
+
+    fn issues(payload: &[u8]) -> Vec<ValidationIssue> {
+        parse_mrp_data(payload).unwrap().validate()
+    }
+
+    #[test]
+    fn test_reference_payload_is_valid() {
+        assert_eq!(issues(&TEST_PAYLOAD), vec![]);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let mut payload = TEST_PAYLOAD;
+        payload[1] = 0x02;
+        let issue = ValidationIssue::UnsupportedVersion(0x0002);
+        assert_eq!(issues(&payload), vec![issue.clone()]);
+        assert_eq!(issue.severity(), Severity::Warning);
+        assert_eq!(issue.to_string(), "MRP version 0x0002 is not 0x0001");
+    }
+
+    #[test]
+    fn test_missing_end_tlv() {
+        let issue = ValidationIssue::MissingEndTlv;
+        assert_eq!(issues(&TEST_PAYLOAD[..50]), vec![issue.clone()]);
+        assert_eq!(issue.severity(), Severity::Error);
+        assert_eq!(issue.to_string(), "PDU has no MRP_End TLV");
+    }
+
+    #[test]
+    fn test_common_count() {
+        let mut duplicate = TEST_PAYLOAD[..42].to_vec();
+        duplicate.extend_from_slice(&COMMON);
+        duplicate.extend_from_slice(&[0x00, 0x00]);
+        let issue = ValidationIssue::DuplicateCommon;
+        assert_eq!(issues(&duplicate), vec![issue.clone()]);
+        assert_eq!(issue.to_string(), "PDU has more than one MRP_Common TLV");
+
+        let mut missing = TEST_PAYLOAD[..22].to_vec();
+        missing.extend_from_slice(&[0x00, 0x00]);
+        let issue = ValidationIssue::MissingCommon;
+        assert_eq!(issues(&missing), vec![issue.clone()]);
+        assert_eq!(issue.to_string(), "PDU has no MRP_Common TLV");
+    }
+
+    #[test]
+    fn test_frame_tlv_placement() {
+        let mut common_first = COMMON.to_vec();
+        common_first.splice(0..0, [0x00, 0x01]);
+        common_first.extend_from_slice(&TEST_PAYLOAD[2..22]);
+        common_first.extend_from_slice(&[0x00, 0x00]);
+        let issue = ValidationIssue::MissingFrameTlv;
+        assert_eq!(issues(&common_first), vec![issue.clone()]);
+        assert_eq!(
+            issue.to_string(),
+            "PDU does not start with an MRP frame TLV"
+        );
+
+        let mut two_tests = TEST_PAYLOAD[..22].to_vec();
+        two_tests.extend_from_slice(&TEST_PAYLOAD[2..]);
+        let issue = ValidationIssue::MultipleFrameTlvs;
+        assert_eq!(issues(&two_tests), vec![issue.clone()]);
+        assert_eq!(issue.to_string(), "PDU carries more than one MRP frame TLV");
+    }
+
+    #[test]
+    fn test_end_not_last() {
+        let mut mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        let end = mrp_data.tlv_headers.pop().unwrap();
+        mrp_data.tlv_headers.insert(2, end);
+        let issue = ValidationIssue::EndNotLast;
+        assert_eq!(mrp_data.validate(), vec![issue.clone()]);
+        assert_eq!(issue.to_string(), "MRP_End TLV is not the last TLV");
+    }
+
+    #[test]
+    fn test_wrong_tlv_length() {
+        // Test TLV padded to 20 bytes: the parser accepts it, validation does not.
+        let mut payload = TEST_PAYLOAD[..2].to_vec();
+        payload.extend_from_slice(&[0x02, 0x14]);
+        payload.extend_from_slice(&TEST_PAYLOAD[4..22]);
+        payload.extend_from_slice(&[0x00, 0x00]);
+        payload.extend_from_slice(&TEST_PAYLOAD[22..]);
+        let issue = ValidationIssue::WrongTlvLength {
+            tlv_type: 0x02,
+            expected: 18,
+            actual: 20,
+        };
+        assert_eq!(issues(&payload), vec![issue.clone()]);
+        assert_eq!(
+            issue.to_string(),
+            "TLV type 0x02 has length 20, expected 18"
+        );
+    }
+
+    #[test]
+    fn test_tlv_type_mismatch() {
+        let mut mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        mrp_data.tlv_headers[0].tlv_type = 0x03;
+        let issue = ValidationIssue::TlvTypeMismatch {
+            tlv_type: 0x03,
+            expected: 0x02,
+        };
+        assert_eq!(mrp_data.validate(), vec![issue.clone()]);
+        assert_eq!(
+            issue.to_string(),
+            "TLV type byte 0x03 does not match its data, expected 0x02"
+        );
+    }
+
+    #[test]
+    fn test_misplaced_ed1() {
+        let mut mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        let MRPTLVData::MRPOption(option) = &mut mrp_data.tlv_headers[2].data else {
+            panic!("expected an Option TLV");
+        };
+        option.manufacturer_oui = [0x00, 0x80, 0x63];
+        assert_eq!(
+            mrp_data.validate(),
+            vec![ValidationIssue::MisplacedEd1 { index: 0 }]
+        );
+    }
+
+    #[test]
+    fn test_value_too_long() {
+        let mut mrp_data = parse_mrp_data(&TEST_PAYLOAD).unwrap();
+        mrp_data.tlv_headers[2].data = MRPTLVData::MRPOption(MRPOptionData::new(
+            [0x00, 0x80, 0x63],
+            vec![MRPSubTlv::Raw {
+                sub_type: 0x80,
+                value: vec![0; 251],
+            }],
+        ));
+        let issue = ValidationIssue::ValueTooLong {
+            tlv_type: 0x7f,
+            length: 256,
+        };
+        assert_eq!(mrp_data.validate(), vec![issue.clone()]);
+        assert_eq!(issue.severity(), Severity::Error);
+        assert_eq!(
+            issue.to_string(),
+            "TLV type 0x7f value is 256 bytes, at most 255 can be encoded"
+        );
+    }
+
+    #[test]
+    fn test_unknown_tlv_type() {
+        let mut payload = TEST_PAYLOAD[..42].to_vec();
+        payload.extend_from_slice(&[0x80, 0x01, 0xaa, 0x00, 0x00]);
+        let issue = ValidationIssue::UnknownTlvType(0x80);
+        assert_eq!(issues(&payload), vec![issue.clone()]);
+        assert_eq!(issue.severity(), Severity::Warning);
+        assert_eq!(
+            issue.to_string(),
+            "TLV type 0x80 is not defined by IEC 62439-2"
+        );
+    }
+}